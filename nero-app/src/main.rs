@@ -1,5 +1,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod proxy;
+
+use std::sync::Mutex;
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
@@ -8,7 +12,9 @@ fn greet(name: &str) -> String {
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .manage(Mutex::new(proxy::StreamPolicy::default()))
+        .setup(|app| Ok(proxy::start(app.handle())?))
+        .invoke_handler(tauri::generate_handler![greet, proxy::declare_streams])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
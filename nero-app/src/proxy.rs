@@ -0,0 +1,558 @@
+//! Streaming proxy for videos that require specific HTTP headers.
+//!
+//! Webviews can't attach headers (e.g. Referer) to `<video>` sources, so the UI
+//! points them to `{base}/{headers}/{scheme}/{authority}/{path}` instead, where
+//! `headers` is a percent-encoded JSON list of name/value pairs and `base` is
+//! returned by [`declare_streams`]. Keeping the headers in the path lets relative
+//! URLs (e.g. HLS segments) resolve through the proxy as well, absolute ones are
+//! rewritten in the playlists.
+//!
+//! The proxy is a small HTTP/1.1 server on the loopback interface rather than a
+//! custom URI scheme, since responses of those have to be complete before the
+//! webview gets them. Bodies are streamed as they arrive, except for HLS playlists,
+//! which are rewritten. The base path holds a token generated at launch, so other
+//! programs of the machine can't use the proxy.
+//!
+//! Only the hosts and headers declared with [`declare_streams`] are proxied, so the
+//! proxy can't be used to reach arbitrary hosts.
+
+use std::{
+    collections::{hash_map::RandomState, HashSet},
+    error::Error,
+    hash::{BuildHasher, Hasher},
+    sync::{LazyLock, Mutex, PoisonError},
+};
+
+use futures_util::StreamExt;
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+use tauri::{
+    http::{header, StatusCode},
+    AppHandle, Manager, Runtime, State, Url,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Largest request head read from the webview.
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// Largest playlist buffered to be rewritten, other bodies are streamed.
+const MAX_PLAYLIST_SIZE: usize = 4 * 1024 * 1024;
+
+/// Upstream headers forwarded back to the webview.
+const FORWARDED_HEADERS: [header::HeaderName; 4] = [
+    header::CONTENT_TYPE,
+    header::CONTENT_LENGTH,
+    header::CONTENT_RANGE,
+    header::ACCEPT_RANGES,
+];
+
+/// Origins of the app's own pages in release builds, the only ones allowed to read
+/// proxied responses (e.g. hls.js fetching playlists).
+const APP_ORIGINS: [&str; 3] = [
+    "tauri://localhost",
+    "http://tauri.localhost",
+    "https://tauri.localhost",
+];
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+type ProxyResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Hosts and headers the proxy may use, declared by the videos of the current
+/// extension.
+#[derive(Default)]
+pub struct StreamPolicy {
+    hosts: HashSet<String>,
+    /// Header names, lowercased, and values.
+    headers: HashSet<(String, String)>,
+}
+
+impl StreamPolicy {
+    fn allows_host(&self, host: &str) -> bool {
+        self.hosts.contains(host)
+    }
+
+    fn allows(&self, target: &Url, headers: &[(String, String)]) -> bool {
+        target.host_str().is_some_and(|host| self.allows_host(host))
+            && headers.iter().all(|(name, value)| {
+                self.headers
+                    .contains(&(name.to_ascii_lowercase(), value.clone()))
+            })
+    }
+}
+
+/// Address the proxy listens on, including the token of this launch.
+pub struct ProxyBase(String);
+
+/// A video, as declared to [`declare_streams`].
+#[derive(Deserialize)]
+pub struct DeclaredStream {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+/// Replaces the hosts and headers the proxy accepts with the ones of `streams`,
+/// the videos of the extension currently playing. Returns the base URL proxied
+/// URLs start with.
+#[tauri::command]
+pub fn declare_streams(
+    policy: State<'_, Mutex<StreamPolicy>>,
+    base: State<'_, ProxyBase>,
+    streams: Vec<DeclaredStream>,
+) -> String {
+    let mut policy = policy.lock().unwrap_or_else(PoisonError::into_inner);
+    *policy = StreamPolicy::default();
+
+    for stream in streams {
+        if let Some(host) = Url::parse(&stream.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_owned))
+        {
+            policy.hosts.insert(host);
+        }
+        policy.headers.extend(
+            stream
+                .headers
+                .into_iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value)),
+        );
+    }
+
+    base.0.clone()
+}
+
+/// Starts the proxy on a free loopback port and manages its [`ProxyBase`].
+pub fn start<R: Runtime>(app: &AppHandle<R>) -> std::io::Result<()> {
+    let listener = tauri::async_runtime::block_on(TcpListener::bind("127.0.0.1:0"))?;
+    let token = random_token();
+    let base = format!("http://{}/{token}", listener.local_addr()?);
+    app.manage(ProxyBase(base.clone()));
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let (app, base, token) = (app.clone(), base.clone(), token.clone());
+            tauri::async_runtime::spawn(async move {
+                // The webview closing the connection (e.g. when seeking) isn't an error
+                let _ = serve(&app, socket, &base, &token).await;
+            });
+        }
+    });
+    Ok(())
+}
+
+/// 128 random bits as hex, from the randomly seeded keys of the standard hasher.
+fn random_token() -> String {
+    (0..2)
+        .map(|_| format!("{:016x}", RandomState::new().build_hasher().finish()))
+        .collect()
+}
+
+/// Request of the webview, as far as the proxy cares.
+#[derive(Debug, PartialEq)]
+struct RequestHead {
+    method: String,
+    /// Path and query of the request.
+    target: String,
+    /// Header names, lowercased, and values.
+    headers: Vec<(String, String)>,
+}
+
+impl RequestHead {
+    fn header(&self, name: &header::HeaderName) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name.as_str())
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parses the head of an HTTP/1.1 request, up to the empty line ending it.
+fn parse_head(head: &str) -> Option<RequestHead> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let (method, target) = (request_line.next()?, request_line.next()?);
+
+    let headers = lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_ascii_lowercase(), value.trim().to_owned()))
+        })
+        .collect();
+
+    Some(RequestHead {
+        method: method.to_owned(),
+        target: target.to_owned(),
+        headers,
+    })
+}
+
+async fn read_head(socket: &mut TcpStream) -> ProxyResult<RequestHead> {
+    let mut head = Vec::new();
+    let mut buffer = [0; 1024];
+
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = socket.read(&mut buffer).await?;
+        if read == 0 || head.len() + read > MAX_HEAD_SIZE {
+            return Err("incomplete or oversized request head".into());
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+
+    parse_head(std::str::from_utf8(&head)?).ok_or_else(|| "malformed request head".into())
+}
+
+/// Writes the status line and the headers of a response, which ends with the
+/// connection since the proxy doesn't keep connections alive.
+async fn write_head(
+    socket: &mut TcpStream,
+    status: StatusCode,
+    headers: &[(String, String)],
+) -> ProxyResult<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("Connection: close\r\n\r\n");
+
+    socket.write_all(head.as_bytes()).await?;
+    Ok(())
+}
+
+async fn respond_with(
+    socket: &mut TcpStream,
+    status: StatusCode,
+    mut headers: Vec<(String, String)>,
+    body: &[u8],
+) -> ProxyResult<()> {
+    headers.push((header::CONTENT_LENGTH.to_string(), body.len().to_string()));
+    write_head(socket, status, &headers).await?;
+    socket.write_all(body).await?;
+    Ok(())
+}
+
+async fn serve<R: Runtime>(
+    app: &AppHandle<R>,
+    mut socket: TcpStream,
+    base: &str,
+    token: &str,
+) -> ProxyResult<()> {
+    let request = read_head(&mut socket).await?;
+
+    let mut cors = Vec::new();
+    if let Some(origin) = request
+        .header(&header::ORIGIN)
+        .filter(|origin| is_app_origin(app, origin))
+    {
+        cors.push((
+            header::ACCESS_CONTROL_ALLOW_ORIGIN.to_string(),
+            origin.to_owned(),
+        ));
+        cors.push((header::VARY.to_string(), header::ORIGIN.to_string()));
+    }
+
+    match request.method.as_str() {
+        "GET" => {}
+        // Preflight of requests carrying a range, e.g. from hls.js
+        "OPTIONS" => {
+            cors.push((
+                header::ACCESS_CONTROL_ALLOW_HEADERS.to_string(),
+                header::RANGE.to_string(),
+            ));
+            return respond_with(&mut socket, StatusCode::NO_CONTENT, cors, &[]).await;
+        }
+        _ => return respond_with(&mut socket, StatusCode::METHOD_NOT_ALLOWED, cors, &[]).await,
+    }
+
+    let target = match parse_target(&request.target, base, token) {
+        Ok(target) => target,
+        Err(err) => {
+            let body = err.to_string().into_bytes();
+            return respond_with(&mut socket, StatusCode::BAD_REQUEST, cors, &body).await;
+        }
+    };
+
+    let policy = app.state::<Mutex<StreamPolicy>>();
+    if !policy
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .allows(&target.url, &target.headers)
+    {
+        let body = format!("{} was not declared by the extension", target.url).into_bytes();
+        return respond_with(&mut socket, StatusCode::FORBIDDEN, cors, &body).await;
+    }
+
+    let mut upstream = CLIENT.get(target.url.clone());
+    for (name, value) in &target.headers {
+        upstream = upstream.header(name, value);
+    }
+    if let Some(range) = request.header(&header::RANGE) {
+        upstream = upstream.header(header::RANGE.as_str(), range);
+    }
+    let upstream = match upstream.send().await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let body = err.to_string().into_bytes();
+            return respond_with(&mut socket, StatusCode::BAD_GATEWAY, cors, &body).await;
+        }
+    };
+
+    let status = StatusCode::from_u16(upstream.status().as_u16())?;
+    let playlist = is_playlist(&target.url, &upstream);
+    let mut headers = cors;
+    for name in FORWARDED_HEADERS {
+        // Rewritten playlists don't keep their length
+        if playlist && name == header::CONTENT_LENGTH {
+            continue;
+        }
+        if let Some(value) = upstream
+            .headers()
+            .get(name.as_str())
+            .and_then(|value| value.to_str().ok())
+        {
+            headers.push((name.to_string(), value.to_owned()));
+        }
+    }
+
+    if playlist {
+        let body = read_playlist(upstream).await?;
+        let body = {
+            let policy = policy.lock().unwrap_or_else(PoisonError::into_inner);
+            rewrite_playlist(&body, &target.prefix, |host| policy.allows_host(host))
+        };
+        return respond_with(&mut socket, status, headers, body.as_bytes()).await;
+    }
+
+    write_head(&mut socket, status, &headers).await?;
+    let mut chunks = upstream.bytes_stream();
+    while let Some(chunk) = chunks.next().await {
+        socket.write_all(&chunk?).await?;
+    }
+    Ok(())
+}
+
+/// Upstream request extracted from a proxied path.
+#[derive(Debug)]
+struct Target {
+    url: Url,
+    headers: Vec<(String, String)>,
+    /// Start of the proxied URLs up to the headers, prepended to rewritten URLs.
+    prefix: String,
+}
+
+/// Extracts the upstream URL and the headers to send from the path and query of
+/// a request, `/{token}/{headers}/{scheme}/{authority}/{path}`.
+fn parse_target(target: &str, base: &str, token: &str) -> ProxyResult<Target> {
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (target, None),
+    };
+    let Some(rest) = path
+        .strip_prefix('/')
+        .and_then(|path| path.strip_prefix(token))
+        .and_then(|path| path.strip_prefix('/'))
+    else {
+        return Err("unknown proxy token".into());
+    };
+
+    let mut segments = rest.splitn(3, '/');
+    let (Some(headers), Some(scheme), Some(rest)) =
+        (segments.next(), segments.next(), segments.next())
+    else {
+        return Err(format!("malformed proxy path: {target}").into());
+    };
+    if !matches!(scheme, "http" | "https") {
+        return Err(format!("unsupported scheme: {scheme}").into());
+    }
+
+    let url = match query {
+        Some(query) => format!("{scheme}://{rest}?{query}"),
+        None => format!("{scheme}://{rest}"),
+    };
+
+    Ok(Target {
+        url: Url::parse(&url)?,
+        headers: serde_json::from_str(&percent_decode_str(headers).decode_utf8()?)?,
+        prefix: format!("{base}/{headers}/"),
+    })
+}
+
+fn is_app_origin<R: Runtime>(app: &AppHandle<R>, origin: &str) -> bool {
+    APP_ORIGINS.contains(&origin)
+        || (cfg!(debug_assertions)
+            && app
+                .config()
+                .build
+                .dev_url
+                .as_ref()
+                .is_some_and(|url| url.origin().ascii_serialization() == origin))
+}
+
+fn is_playlist(target: &Url, upstream: &reqwest::Response) -> bool {
+    let content_type = upstream
+        .headers()
+        .get(header::CONTENT_TYPE.as_str())
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    content_type.contains("mpegurl") || target.path().to_ascii_lowercase().ends_with(".m3u8")
+}
+
+/// Reads a playlist to rewrite it, giving up past `MAX_PLAYLIST_SIZE`.
+async fn read_playlist(upstream: reqwest::Response) -> ProxyResult<String> {
+    let mut body = Vec::new();
+    let mut chunks = upstream.bytes_stream();
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > MAX_PLAYLIST_SIZE {
+            return Err(format!("playlist exceeds {MAX_PLAYLIST_SIZE} bytes").into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8(body)?)
+}
+
+/// Points the absolute URLs of an HLS playlist, either on their own line or in a
+/// `URI="..."` attribute, to the proxy when `allowed` accepts their host.
+///
+/// URLs of other hosts are left as they are and loaded without the headers, so a
+/// playlist can't extend the proxy to hosts the extension didn't declare.
+fn rewrite_playlist(playlist: &str, prefix: &str, allowed: impl Fn(&str) -> bool) -> String {
+    let proxied = |url: &str| -> Option<String> {
+        let (scheme, rest) = url.split_once("://")?;
+        if !matches!(scheme, "http" | "https") {
+            return None;
+        }
+        if !allowed(Url::parse(url).ok()?.host_str()?) {
+            return None;
+        }
+        Some(format!("{prefix}{scheme}/{rest}"))
+    };
+
+    playlist
+        .lines()
+        .map(|line| match line.strip_prefix('#') {
+            Some(tag) => match tag.split_once("URI=\"") {
+                Some((before, rest)) => {
+                    let (url, after) = rest.split_once('"').unwrap_or((rest, ""));
+                    match proxied(url) {
+                        Some(url) => format!("#{before}URI=\"{url}\"{after}"),
+                        None => line.to_owned(),
+                    }
+                }
+                None => line.to_owned(),
+            },
+            None => proxied(line.trim()).unwrap_or_else(|| line.to_owned()),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "http://127.0.0.1:4000/token";
+    const PREFIX: &str = "http://127.0.0.1:4000/token/%5B%5D/";
+
+    #[test]
+    fn parse_head_reads_method_target_and_headers() {
+        let head = parse_head(
+            "GET /a/b?c HTTP/1.1\r\nRange: bytes=0-\r\nORIGIN:  tauri://localhost\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(head.method, "GET");
+        assert_eq!(head.target, "/a/b?c");
+        assert_eq!(head.header(&header::RANGE), Some("bytes=0-"));
+        assert_eq!(head.header(&header::ORIGIN), Some("tauri://localhost"));
+        assert_eq!(head.header(&header::COOKIE), None);
+    }
+
+    #[test]
+    fn parse_head_rejects_a_missing_target() {
+        assert_eq!(parse_head("GET\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn parse_target_extracts_url_headers_and_prefix() {
+        let headers = "%5B%5B%22Referer%22%2C%22https%3A%2F%2Fsite.com%22%5D%5D";
+        let target = parse_target(
+            &format!("/token/{headers}/https/cdn.com/video/index.m3u8?expires=1&sig=a"),
+            BASE,
+            "token",
+        )
+        .unwrap();
+
+        assert_eq!(
+            target.url.as_str(),
+            "https://cdn.com/video/index.m3u8?expires=1&sig=a"
+        );
+        assert_eq!(
+            target.headers,
+            [("Referer".to_owned(), "https://site.com".to_owned())]
+        );
+        assert_eq!(target.prefix, format!("{BASE}/{headers}/"));
+    }
+
+    #[test]
+    fn parse_target_rejects_another_token() {
+        assert!(parse_target("/other/%5B%5D/https/cdn.com/a", BASE, "token").is_err());
+        assert!(parse_target("/tokenx/%5B%5D/https/cdn.com/a", BASE, "token").is_err());
+    }
+
+    #[test]
+    fn parse_target_rejects_other_schemes_and_short_paths() {
+        assert!(parse_target("/token/%5B%5D/file/etc/passwd", BASE, "token").is_err());
+        assert!(parse_target("/token/%5B%5D/https", BASE, "token").is_err());
+        assert!(parse_target("/token/not-json/https/cdn.com/a", BASE, "token").is_err());
+    }
+
+    #[test]
+    fn rewrite_playlist_proxies_absolute_urls_of_allowed_hosts() {
+        let playlist = "#EXTM3U\nhttps://cdn.com/a.ts\n  http://cdn.com/b.ts  ";
+        assert_eq!(
+            rewrite_playlist(playlist, PREFIX, |host| host == "cdn.com"),
+            format!("#EXTM3U\n{PREFIX}https/cdn.com/a.ts\n{PREFIX}http/cdn.com/b.ts")
+        );
+    }
+
+    #[test]
+    fn rewrite_playlist_keeps_relative_urls() {
+        let playlist = "#EXTM3U\nsegment-1.ts\n/absolute/path.ts";
+        assert_eq!(rewrite_playlist(playlist, PREFIX, |_| true), playlist);
+    }
+
+    #[test]
+    fn rewrite_playlist_rewrites_uri_attributes() {
+        let playlist = "#EXT-X-KEY:METHOD=AES-128,URI=\"https://cdn.com/key\",IV=0x1\n\
+                        #EXT-X-MAP:URI=\"init.mp4\"";
+        assert_eq!(
+            rewrite_playlist(playlist, PREFIX, |_| true),
+            format!(
+                "#EXT-X-KEY:METHOD=AES-128,URI=\"{PREFIX}https/cdn.com/key\",IV=0x1\n\
+                 #EXT-X-MAP:URI=\"init.mp4\""
+            )
+        );
+    }
+
+    #[test]
+    fn rewrite_playlist_leaves_undeclared_hosts() {
+        let playlist = "https://other.com/a.ts\n#EXT-X-KEY:URI=\"https://other.com/key\"\n\
+                        data:text/plain,a";
+        assert_eq!(
+            rewrite_playlist(playlist, PREFIX, |host| host == "cdn.com"),
+            playlist
+        );
+    }
+}
@@ -1,3 +1,87 @@
+use std::{env, fmt::Write, fs, path::Path};
+
 fn main() {
+    // Declaring any file to watch replaces the default of rerunning on every change
+    // in the package, so the sources scanned for classes are declared as well
+    println!("cargo:rerun-if-changed=src");
     rustwind::build("../target/classes.txt", &["./src/**/*.rs"]).expect("Failed to build classes");
+    generate_icons("./icons").expect("Failed to generate icons");
+}
+
+/// Generates the `IconType` enum from the SVG files in `dir`, one variant per
+/// file named after it in pascal case (`arrow-left.svg` becomes `ArrowLeft`).
+fn generate_icons(dir: &str) -> std::io::Result<()> {
+    println!("cargo:rerun-if-changed={dir}");
+
+    let mut icons = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "svg"))
+        .collect::<Vec<_>>();
+    icons.sort();
+
+    let mut variants = String::new();
+    let mut arms = String::new();
+    for path in &icons {
+        let name = pascal_case(&path.file_stem().unwrap().to_string_lossy());
+        let svg = fs::read_to_string(path)?;
+
+        let view_box = attribute(&svg, "viewBox")
+            .unwrap_or_else(|| panic!("{} has no viewBox", path.display()));
+        // Outline icons disable the fill of the root element and draw strokes instead
+        let root = &svg[svg.find("<svg").unwrap_or(0)..];
+        let root = &root[..root.find('>').unwrap_or(root.len())];
+        let stroke_width = match attribute(root, "fill") {
+            Some("none") => format!("Some({:?})", attribute(root, "stroke-width").unwrap_or("1")),
+            _ => "None".to_owned(),
+        };
+        let paths = svg
+            .split("<path")
+            .skip(1)
+            .filter_map(|element| attribute(element, "d"))
+            .map(|d| format!("{d:?}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(variants, "    {name},").unwrap();
+        writeln!(
+            arms,
+            "            IconType::{name} => ({view_box:?}, &[{paths}], {stroke_width}),"
+        )
+        .unwrap();
+    }
+
+    let code = format!(
+        "pub enum IconType {{\n{variants}}}\n\n\
+         impl IconType {{\n    \
+             /// Returns the view box, the paths and, for outline icons, the stroke width.\n    \
+             fn attributes(&self) -> (&'static str, &'static [&'static str], Option<&'static str>) {{\n        \
+                 match self {{\n{arms}        }}\n    \
+             }}\n\
+         }}\n"
+    );
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join("icons.rs"),
+        code,
+    )
+}
+
+/// Value of the first `name="..."` attribute found in `element`.
+fn attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!(" {name}=\""))? + name.len() + 3;
+    let len = element[start..].find('"')?;
+    Some(&element[start..start + len])
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
 }
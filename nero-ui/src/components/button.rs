@@ -2,30 +2,85 @@ use rustwind::{
     active,
     backgrounds::BackgroundColor,
     borders::BorderRadius,
-    effects::BoxShadow,
+    effects::{BoxShadow, Opacity},
     flexbox_grid::{AlignItems, Gap},
+    interactivity::Cursor,
     layout::Display,
     spacing::Padding,
     transforms::Scale,
     transitions_animation::TransitionDuration,
+    typography::{FontSize, TextColor},
 };
-use sycamore::web::{
-    events::{click, MouseEvent},
-    tags::{button as button_tag, div, span},
-    GlobalAttributes, GlobalProps, HtmlGlobalAttributes, View,
+use sycamore::{
+    prelude::{create_memo, HtmlButtonAttributes, MaybeDyn},
+    web::{
+        events::{click, MouseEvent},
+        tags::{button as button_tag, div, span},
+        GlobalAttributes, GlobalProps, HtmlGlobalAttributes, View,
+    },
 };
 
-use crate::tw;
+use crate::{
+    hooks::{use_theme, ColorScheme},
+    tw,
+    utils::ViewBuilder,
+};
+
+use super::{AnimatedIcon, DropdownItem, DropdownMenu, IconState};
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum ButtonVariant {
+    /// Main action of a view.
+    Primary,
+    Secondary,
+    /// No background, e.g. for icon buttons over other content.
+    #[default]
+    Ghost,
+    /// Destructive actions, such as deleting something.
+    Danger,
+}
+
+impl ButtonVariant {
+    fn classes(self, theme: ColorScheme) -> &'static str {
+        match self {
+            ButtonVariant::Primary => tw!(BackgroundColor::Red300, TextColor::Black),
+            ButtonVariant::Secondary => theme.control(),
+            ButtonVariant::Ghost => tw!(BackgroundColor::Transparent),
+            ButtonVariant::Danger => tw!(BackgroundColor::Red500, TextColor::White),
+        }
+    }
+}
 
-use super::Icon;
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum ButtonSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl ButtonSize {
+    fn classes(self) -> &'static str {
+        match self {
+            ButtonSize::Small => tw!(Padding::Px2, Padding::Py1, FontSize::Sm),
+            ButtonSize::Medium => tw!(Padding::Px3, Padding::Py1_5),
+            ButtonSize::Large => tw!(Padding::Px4, Padding::Py2, FontSize::Lg),
+        }
+    }
+}
 
 pub struct Button<T>
 where
     T: FnMut(MouseEvent) + 'static,
 {
-    children: View,
+    icon: Option<View>,
+    children: Option<View>,
+    variant: ButtonVariant,
+    size: ButtonSize,
     color: Option<BackgroundColor>,
     box_shadow: Option<BoxShadow>,
+    loading: MaybeDyn<bool>,
+    disabled: MaybeDyn<bool>,
     on_click: T,
 }
 
@@ -35,13 +90,29 @@ where
 {
     pub fn new(children: impl Into<View>, on_click: T) -> Self {
         Self {
-            children: children.into(),
+            icon: None,
+            children: Some(children.into()),
+            variant: ButtonVariant::default(),
+            size: ButtonSize::default(),
             color: None,
             box_shadow: None,
+            loading: false.into(),
+            disabled: false.into(),
             on_click,
         }
     }
 
+    pub fn variant(mut self, variant: ButtonVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn size(mut self, size: ButtonSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Overrides the background color of the variant.
     pub fn color(mut self, color: BackgroundColor) -> Self {
         self.color = Some(color);
         self
@@ -52,46 +123,174 @@ where
         self
     }
 
+    /// Shows a spinner in place of the icon and ignores clicks while `true`.
+    pub fn loading(mut self, loading: impl Into<MaybeDyn<bool>>) -> Self {
+        self.loading = loading.into();
+        self
+    }
+
+    pub fn disabled(mut self, disabled: impl Into<MaybeDyn<bool>>) -> Self {
+        self.disabled = disabled.into();
+        self
+    }
+
     pub fn label(label: &'static str, on_click: T) -> Self {
         Self::new(span().children(label), on_click)
     }
 
-    pub fn icon(icon: Icon, on_click: T) -> Self {
-        Self::new(icon, on_click)
+    pub fn icon(icon: impl Into<View>, on_click: T) -> Self {
+        Self {
+            icon: Some(icon.into()),
+            children: None,
+            ..Self::new(View::default(), on_click)
+        }
     }
 
-    pub fn icon_label(icon: Icon, label: &'static str, on_click: T) -> Self {
-        Self::new(
-            div()
-                .class(tw!(Display::Flex, Gap::_2, AlignItems::Center))
-                .children(icon)
-                .children(span().children(label)),
-            on_click,
-        )
+    pub fn icon_label(icon: impl Into<View>, label: impl Into<View>, on_click: T) -> Self {
+        Self {
+            icon: Some(icon.into()),
+            ..Self::new(span().children(label), on_click)
+        }
         .box_shadow(BoxShadow::Lg)
     }
 }
 
 impl<T: FnMut(MouseEvent)> From<Button<T>> for View {
     fn from(button: Button<T>) -> Self {
+        let loading = button.loading;
+        let disabled = button.disabled;
+        let loading = create_memo(move || loading.get());
+        // Disabled buttons don't fire click events, which also covers loading ones
+        let inactive = create_memo(move || loading.get() || disabled.get());
+        let theme = use_theme();
+        let (variant, color) = (button.variant, button.color);
+
+        let base = format!(
+            "{} {} {}",
+            tw!(
+                BorderRadius::Lg,
+                TransitionDuration::_300,
+                active!(Scale::_95)
+            ),
+            button.size.classes(),
+            button.box_shadow.unwrap_or(BoxShadow::None).as_class()
+        );
+
+        // The spinner takes the place of the icon, or goes before the content without one
+        let spinner = span()
+            .class(move || match loading.get() {
+                true => tw!(Display::Contents),
+                false => tw!(Display::Hidden),
+            })
+            .children(AnimatedIcon::new(IconState::refresh(loading)).size("16"));
+
         button_tag()
-            .class(format!(
-                "{} {} {}",
-                tw!(
-                    Padding::Px3,
-                    Padding::Py1_5,
-                    BorderRadius::Lg,
-                    TransitionDuration::_300,
-                    active!(Scale::_95)
-                ),
-                button
-                    .color
-                    .unwrap_or(BackgroundColor::Transparent)
-                    .as_class(),
-                button.box_shadow.unwrap_or(BoxShadow::None).as_class()
-            ))
-            .children(button.children)
+            .class(move || {
+                let color = color.map_or(variant.classes(theme.get()), |color| color.as_class());
+                match inactive.get() {
+                    true => format!("{base} {color} {}", tw!(Opacity::_50, Cursor::NotAllowed)),
+                    false => format!("{base} {color}"),
+                }
+            })
+            .disabled(inactive)
+            .attr("aria-busy", move || loading.get().to_string())
+            .children(
+                div()
+                    .class(tw!(Display::Flex, Gap::_2, AlignItems::Center))
+                    .children(spinner)
+                    .when_some(button.icon, |this, icon| {
+                        this.children(
+                            span()
+                                .class(move || match loading.get() {
+                                    true => tw!(Display::Hidden),
+                                    false => tw!(Display::Contents),
+                                })
+                                .children(icon),
+                        )
+                    })
+                    .when_some(button.children, |this, children| this.children(children)),
+            )
             .on(click, button.on_click)
             .into()
     }
 }
+
+/// Buttons joined into a single control sharing the background of `variant`.
+///
+/// Its buttons should be ghost buttons, so they don't cover that background.
+pub struct ButtonGroup {
+    children: Vec<View>,
+    variant: ButtonVariant,
+}
+
+impl ButtonGroup {
+    pub fn new(children: Vec<View>) -> Self {
+        Self {
+            children,
+            variant: ButtonVariant::Secondary,
+        }
+    }
+
+    pub fn variant(mut self, variant: ButtonVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+}
+
+impl From<ButtonGroup> for View {
+    fn from(group: ButtonGroup) -> Self {
+        let theme = use_theme();
+
+        div()
+            .class(move || {
+                format!(
+                    "{} {}",
+                    tw!(
+                        Display::Flex,
+                        AlignItems::Center,
+                        BorderRadius::Lg,
+                        BoxShadow::Lg
+                    ),
+                    group.variant.classes(theme.get())
+                )
+            })
+            .children(group.children)
+            .into()
+    }
+}
+
+/// Main action with a dropdown of alternatives next to it, e.g. where to start
+/// watching a series from.
+pub struct SplitButton<T>
+where
+    T: FnMut(MouseEvent) + 'static,
+{
+    button: Button<T>,
+    items: Vec<DropdownItem>,
+}
+
+impl<T> SplitButton<T>
+where
+    T: FnMut(MouseEvent),
+{
+    pub fn new(button: Button<T>, items: Vec<DropdownItem>) -> Self {
+        Self { button, items }
+    }
+}
+
+impl<T: FnMut(MouseEvent)> From<SplitButton<T>> for View {
+    fn from(split: SplitButton<T>) -> Self {
+        let variant = split.button.variant;
+
+        ButtonGroup::new(vec![
+            split
+                .button
+                .variant(ButtonVariant::Ghost)
+                .box_shadow(BoxShadow::None)
+                .into(),
+            DropdownMenu::new(span().children("▾"), split.items).into(),
+        ])
+        .variant(variant)
+        .into()
+    }
+}
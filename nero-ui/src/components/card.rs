@@ -2,25 +2,33 @@ use rustwind::{
     active,
     backgrounds::BackgroundColor,
     borders::BorderRadius,
-    flexbox_grid::{AlignItems, FlexDirection, Gap, JustifyContent},
-    hover,
+    flexbox_grid::{AlignItems, FlexDirection, FlexShrink, Gap, JustifyContent},
     interactivity::Cursor,
-    layout::{AspectRatio, Display, ObjectFit},
-    sizing::Width,
+    layout::{AspectRatio, Display, ObjectFit, Overflow, Position, TopRightBottomLeft},
+    sizing::{Height, Width},
     spacing::Padding,
     transforms::Scale,
     transitions_animation::TransitionDuration,
     typography::{FontSize, FontWeight, LineClamp, TextColor, TextOverflow},
 };
 use sycamore::{
-    prelude::HtmlImgAttributes,
+    prelude::create_memo,
     web::{
-        tags::{div, h3, img, p, span, HtmlDiv},
+        tags::{div, h3, p, span, HtmlDiv},
         GlobalProps, HtmlGlobalAttributes, View,
     },
 };
 
-use crate::{tw, types::Episode, utils::ViewBuilder};
+use crate::{
+    format,
+    hooks::{use_app_state, use_theme},
+    storage::HistoryEntry,
+    tw,
+    types::{Episode, Series},
+    utils::ViewBuilder,
+};
+
+use super::{Image, ProgressBar, ProgressRing, RichText};
 
 pub trait IntoSmallCard<T: Into<View>> {
     fn into_small_card(self) -> T;
@@ -38,30 +46,73 @@ const BASE_EPISODE_CARD_CLASSES: &str = tw!(
     Cursor::Pointer,
     BorderRadius::Md,
     TransitionDuration::_300,
-    hover!(BackgroundColor::Gray100),
     active!(Scale::_95)
 );
 
+/// Wraps an episode thumbnail with a ring showing how much of it was watched,
+/// which follows the player while the episode is playing.
+fn with_progress_ring(
+    thumbnail: Image,
+    series_id: String,
+    episode_id: String,
+    saved: Option<f64>,
+    width: Width,
+) -> View {
+    let now_playing = use_app_state().now_playing;
+    let progress = create_memo(move || {
+        now_playing.with(|now_playing| match now_playing {
+            // Episode ids are only unique within a series
+            Some(now_playing)
+                if now_playing.series_id == series_id && now_playing.episode_id == episode_id =>
+            {
+                Some(now_playing.progress)
+            }
+            _ => saved,
+        })
+    });
+
+    div()
+        .class(format!("{} {}", tw!(Position::Relative), width.as_class()))
+        .children(thumbnail.class(tw!(
+            Width::Full,
+            BorderRadius::Lg,
+            AspectRatio::Video,
+            ObjectFit::Cover
+        )))
+        .children(
+            div()
+                .class(move || match progress.get() {
+                    Some(progress) if progress > 0.0 => tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Bottom2,
+                        TopRightBottomLeft::Right2
+                    ),
+                    _ => tw!(Display::Hidden),
+                })
+                .children(ProgressRing::new(move || {
+                    progress.get().unwrap_or_default()
+                })),
+        )
+        .into()
+}
+
 impl IntoSmallCard<HtmlDiv> for Episode {
     fn into_small_card(self) -> HtmlDiv {
+        let theme = use_theme();
+        let title = self
+            .title
+            .clone()
+            .unwrap_or(format!("Episode {}", self.number));
+
         div()
-            .class(BASE_EPISODE_CARD_CLASSES)
-            .children(
-                img()
-                    .class(tw!(
-                        Width::_1over2,
-                        BorderRadius::Lg,
-                        AspectRatio::Video,
-                        ObjectFit::Cover
-                    ))
-                    // TODO: use a default thumbnail if none is provided
-                    .src(self.thumbnail_url.unwrap_or_default())
-                    .alt(
-                        self.title
-                            .clone()
-                            .unwrap_or(format!("Episode {}", self.number)),
-                    ),
-            )
+            .class(move || format!("{BASE_EPISODE_CARD_CLASSES} {}", theme.get().hover()))
+            .children(with_progress_ring(
+                Image::new(self.thumbnail_url, title),
+                self.series_id,
+                self.id,
+                self.progress,
+                Width::_1over2,
+            ))
             .children(
                 div()
                     .class(tw!(
@@ -87,9 +138,10 @@ impl IntoSmallCard<HtmlDiv> for Episode {
 impl IntoCard<HtmlDiv> for Episode {
     fn into_card(self) -> HtmlDiv {
         let title = self.title.unwrap_or(format!("Episode {}", self.number));
+        let theme = use_theme();
 
         div()
-            .class(BASE_EPISODE_CARD_CLASSES)
+            .class(move || format!("{BASE_EPISODE_CARD_CLASSES} {}", theme.get().hover()))
             .children(
                 span()
                     .class(tw!(
@@ -100,28 +152,98 @@ impl IntoCard<HtmlDiv> for Episode {
                     ))
                     .children(self.number),
             )
+            .children(with_progress_ring(
+                Image::new(self.thumbnail_url, title.clone()),
+                self.series_id,
+                self.id,
+                self.progress,
+                Width::_4over12,
+            ))
             .children(
-                img()
+                div()
+                    .class(tw!(Display::Flex, FlexDirection::Col, Width::_7over12))
+                    .children(h3().class(tw!(FontWeight::Semibold)).children(title))
+                    .when_some(self.description, |this, description| {
+                        this.children(RichText::new(&description).class(tw!(
+                            TextColor::Gray500,
+                            FontSize::Sm,
+                            LineClamp::_3
+                        )))
+                    }),
+            )
+    }
+}
+
+impl IntoCard<HtmlDiv> for Series {
+    fn into_card(self) -> HtmlDiv {
+        div()
+            .class(tw!(
+                Display::Flex,
+                FlexDirection::Col,
+                Gap::_2,
+                Cursor::Pointer,
+                TransitionDuration::_300,
+                active!(Scale::_95)
+            ))
+            .children(
+                Image::new(self.poster_url, self.title.clone())
                     .class(tw!(
-                        Width::_4over12,
+                        Width::Full,
+                        Height::_64,
                         BorderRadius::Lg,
-                        AspectRatio::Video,
                         ObjectFit::Cover
                     ))
-                    // TODO: use a default thumbnail if none is provided
-                    .src(self.thumbnail_url.unwrap_or_default())
-                    .alt(title.clone()),
+                    .cached(true),
             )
+            .children(
+                h3().class(tw!(TextOverflow::Truncate, FontWeight::Semibold))
+                    .children(self.title),
+            )
+            .when_some(self.r#type, |this, r#type| {
+                this.children(
+                    p().class(tw!(TextColor::Gray500, FontSize::Sm))
+                        .children(r#type),
+                )
+            })
+    }
+}
+
+impl IntoCard<HtmlDiv> for HistoryEntry {
+    fn into_card(self) -> HtmlDiv {
+        let progress = self.progress();
+
+        div()
+            .class(tw!(
+                Display::Flex,
+                FlexDirection::Col,
+                FlexShrink::_0,
+                Gap::_2,
+                Width::_40,
+                Cursor::Pointer,
+                TransitionDuration::_300,
+                active!(Scale::_95)
+            ))
             .children(
                 div()
-                    .class(tw!(Display::Flex, FlexDirection::Col, Width::_7over12))
-                    .children(h3().class(tw!(FontWeight::Semibold)).children(title))
-                    .when_some(self.description, |this, description| {
-                        this.children(
-                            p().class(tw!(TextColor::Gray500, FontSize::Sm, LineClamp::_3))
-                                .children(description),
-                        )
-                    }),
+                    .class(tw!(BorderRadius::Lg, Overflow::Hidden))
+                    .children(
+                        Image::new(self.poster_url, self.series_title.clone())
+                            .class(tw!(Width::Full, Height::_56, ObjectFit::Cover))
+                            .cached(true),
+                    )
+                    .children(ProgressBar::new(progress)),
+            )
+            .children(
+                h3().class(tw!(TextOverflow::Truncate, FontWeight::Semibold))
+                    .children(self.series_title),
+            )
+            .children(
+                p().class(tw!(TextColor::Gray500, FontSize::Sm))
+                    .children(format!(
+                        "Episode {} · {}",
+                        self.episode_number,
+                        format::relative_time(self.watched_at)
+                    )),
             )
     }
 }
@@ -0,0 +1,143 @@
+use std::{cell::RefCell, rc::Rc};
+
+use rustwind::{
+    backgrounds::BackgroundColor,
+    effects::Opacity,
+    flexbox_grid::{AlignItems, FlexDirection, Gap, JustifyContent},
+    layout::{Display, Position, TopRightBottomLeft, ZIndex},
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{create_signal, on_cleanup, Signal},
+    web::{
+        tags::{div, p, span},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+use crate::tw;
+
+use super::{Button, ButtonVariant};
+
+type Callback = Rc<RefCell<dyn FnMut()>>;
+
+/// Overlay counting down before running an action, which the user can
+/// either cancel or trigger right away.
+pub struct CountdownOverlay {
+    label: String,
+    seconds: u32,
+    on_finish: Callback,
+    on_cancel: Callback,
+}
+
+impl CountdownOverlay {
+    pub fn new(
+        label: impl Into<String>,
+        seconds: u32,
+        on_finish: impl FnMut() + 'static,
+        on_cancel: impl FnMut() + 'static,
+    ) -> Self {
+        Self {
+            label: label.into(),
+            seconds,
+            on_finish: Rc::new(RefCell::new(on_finish)),
+            on_cancel: Rc::new(RefCell::new(on_cancel)),
+        }
+    }
+
+    /// Runs the callback once the current event handler has returned, since
+    /// it usually disposes the overlay (and the timer calling it) altogether.
+    fn defer(callback: Callback) {
+        wasm_bindgen_futures::spawn_local(async move { (&mut *callback.borrow_mut())() });
+    }
+
+    /// Decrements `remaining` every second until the overlay is disposed, running
+    /// `on_finish` when it reaches zero.
+    fn start_timer(remaining: Signal<u32>, on_finish: Callback) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        let tick = Closure::<dyn FnMut()>::new(move || match remaining.get() {
+            0 => {}
+            1 => {
+                remaining.set(0);
+                CountdownOverlay::defer(on_finish.clone());
+            }
+            seconds => remaining.set(seconds - 1),
+        });
+        let interval = window
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                tick.as_ref().unchecked_ref(),
+                1000,
+            )
+            .ok();
+
+        on_cleanup(move || {
+            if let Some(interval) = interval {
+                window.clear_interval_with_handle(interval);
+            }
+            drop(tick);
+        });
+    }
+}
+
+impl From<CountdownOverlay> for View {
+    fn from(overlay: CountdownOverlay) -> Self {
+        let remaining = create_signal(overlay.seconds);
+        let on_finish = overlay.on_finish;
+        let on_cancel = overlay.on_cancel;
+
+        CountdownOverlay::start_timer(remaining, on_finish.clone());
+
+        div()
+            .class(tw!(
+                Position::Absolute,
+                TopRightBottomLeft::Inset0,
+                ZIndex::_20
+            ))
+            .children(div().class(tw!(
+                Position::Absolute,
+                TopRightBottomLeft::Inset0,
+                BackgroundColor::Black,
+                Opacity::_75
+            )))
+            .children(
+                div()
+                    .class(tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Inset0,
+                        Display::Flex,
+                        FlexDirection::Col,
+                        AlignItems::Center,
+                        JustifyContent::Center,
+                        Gap::_4,
+                        TextColor::White
+                    ))
+                    .children(p().class(tw!(FontSize::Lg)).children(overlay.label))
+                    .children(
+                        span()
+                            .class(tw!(FontSize::_5xl, FontWeight::Bold))
+                            .children(move || remaining.get().to_string()),
+                    )
+                    .children(
+                        div()
+                            .class(tw!(Display::Flex, Gap::_4))
+                            .children(
+                                Button::label("Play now", move |_| {
+                                    CountdownOverlay::defer(on_finish.clone())
+                                })
+                                .variant(ButtonVariant::Primary),
+                            )
+                            .children(
+                                Button::label("Cancel", move |_| {
+                                    CountdownOverlay::defer(on_cancel.clone())
+                                })
+                                .color(BackgroundColor::Gray500),
+                            ),
+                    ),
+            )
+            .into()
+    }
+}
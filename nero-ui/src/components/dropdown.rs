@@ -0,0 +1,593 @@
+use std::rc::Rc;
+
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    effects::BoxShadow,
+    flexbox_grid::{FlexDirection, FlexWrap, Gap},
+    layout::{Display, Overflow, Position, TopRightBottomLeft, ZIndex},
+    sizing::Width,
+    spacing::Padding,
+    typography::{FontSize, TextAlign, TextColor},
+};
+use sycamore::{
+    prelude::{
+        create_effect, create_memo, create_node_ref, create_signal, HtmlInputAttributes,
+        ReadSignal, Signal,
+    },
+    web::{
+        bind,
+        events::{
+            click, focus, input as input_event, keydown, mouseenter, scroll, Event, KeyboardEvent,
+        },
+        tags::{button, div, input, p, span},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Element, HtmlElement};
+
+use crate::{hooks::use_theme, tw};
+
+use super::Button;
+
+#[derive(Clone)]
+pub struct DropdownItem {
+    label: String,
+    selected: bool,
+    on_select: Rc<dyn Fn()>,
+}
+
+impl DropdownItem {
+    pub fn new(label: impl Into<String>, on_select: impl Fn() + 'static) -> Self {
+        Self {
+            label: label.into(),
+            selected: false,
+            on_select: Rc::new(on_select),
+        }
+    }
+
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+}
+
+/// Button opening a list of actions below it, which can also be browsed
+/// with the arrow keys and picked with Enter.
+pub struct DropdownMenu {
+    trigger: View,
+    items: Vec<DropdownItem>,
+    upward: bool,
+}
+
+impl DropdownMenu {
+    pub fn new(trigger: impl Into<View>, items: Vec<DropdownItem>) -> Self {
+        Self {
+            trigger: trigger.into(),
+            items,
+            upward: false,
+        }
+    }
+
+    /// Opens the list above the trigger, for menus near the bottom of the screen.
+    pub fn upward(mut self) -> Self {
+        self.upward = true;
+        self
+    }
+
+    fn item(
+        item: DropdownItem,
+        index: usize,
+        open: Signal<bool>,
+        highlighted: Signal<Option<usize>>,
+    ) -> View {
+        let theme = use_theme();
+        let selected = item.selected;
+        let on_select = item.on_select;
+
+        button()
+            .class(move || {
+                let background = match (selected, highlighted.get() == Some(index)) {
+                    (true, _) => BackgroundColor::Red300,
+                    (false, true) => theme.get().surface(),
+                    (false, false) => BackgroundColor::Transparent,
+                };
+                format!(
+                    "{} {}",
+                    tw!(
+                        Width::Full,
+                        Padding::Px3,
+                        Padding::Py1_5,
+                        BorderRadius::Md,
+                        TextAlign::Left,
+                        FontSize::Sm
+                    ),
+                    background.as_class()
+                )
+            })
+            .children(span().children(item.label))
+            .on(mouseenter, move |_| highlighted.set(Some(index)))
+            .on(click, move |_| {
+                open.set(false);
+                on_select();
+            })
+            .into()
+    }
+}
+
+impl From<DropdownMenu> for View {
+    fn from(menu: DropdownMenu) -> Self {
+        let open = create_signal(false);
+        let highlighted = create_signal(None::<usize>);
+        let items = Rc::new(menu.items);
+        let theme = use_theme();
+        let position = match menu.upward {
+            true => TopRightBottomLeft::BottomFull,
+            false => TopRightBottomLeft::TopFull,
+        }
+        .as_class();
+
+        let on_keydown = {
+            let items = items.clone();
+            move |event: KeyboardEvent| {
+                let count = items.len();
+                if count == 0 {
+                    return;
+                }
+
+                match event.key().as_str() {
+                    "ArrowDown" => {
+                        open.set(true);
+                        highlighted.set(Some(highlighted.get().map_or(0, |i| (i + 1) % count)));
+                    }
+                    "ArrowUp" => {
+                        open.set(true);
+                        highlighted.set(Some(
+                            highlighted
+                                .get()
+                                .map_or(count - 1, |i| (i + count - 1) % count),
+                        ));
+                    }
+                    "Enter" if open.get() => {
+                        let Some(index) = highlighted.get() else {
+                            return;
+                        };
+                        open.set(false);
+                        (items[index].on_select)();
+                    }
+                    "Escape" if open.get() => open.set(false),
+                    _ => return,
+                }
+
+                // Keeps the keys from reaching the page shortcuts
+                event.prevent_default();
+                event.stop_propagation();
+            }
+        };
+
+        let list = move || {
+            if !open.get() {
+                return View::default();
+            }
+
+            (
+                // Closes the menu when clicking anywhere else
+                div()
+                    .class(tw!(
+                        Position::Fixed,
+                        TopRightBottomLeft::Inset0,
+                        ZIndex::_10
+                    ))
+                    .on(click, move |_| open.set(false)),
+                div()
+                    .class(move || {
+                        format!(
+                            "{} {position} {}",
+                            tw!(
+                                Position::Absolute,
+                                TopRightBottomLeft::Right0,
+                                ZIndex::_20,
+                                Display::Flex,
+                                FlexDirection::Col,
+                                Gap::_1,
+                                Width::_48,
+                                Padding::P1,
+                                BorderRadius::Lg,
+                                BoxShadow::Lg
+                            ),
+                            theme.get().page()
+                        )
+                    })
+                    .children(
+                        items
+                            .iter()
+                            .cloned()
+                            .enumerate()
+                            .map(|(index, item)| DropdownMenu::item(item, index, open, highlighted))
+                            .collect::<Vec<_>>(),
+                    ),
+            )
+                .into()
+        };
+
+        div()
+            .class(tw!(Position::Relative))
+            .on(keydown, on_keydown)
+            .children(Button::new(menu.trigger, move |_| {
+                highlighted.set(None);
+                open.set(!open.get());
+            }))
+            .children(list)
+            .into()
+    }
+}
+
+/// Dropdown picking one of `options`, showing the label of the selected one.
+pub struct Select<T: Clone + PartialEq + 'static> {
+    options: Vec<(String, T)>,
+    selected: ReadSignal<T>,
+    on_change: Rc<dyn Fn(T)>,
+}
+
+impl<T: Clone + PartialEq + 'static> Select<T> {
+    pub fn new(
+        options: Vec<(String, T)>,
+        selected: ReadSignal<T>,
+        on_change: impl Fn(T) + 'static,
+    ) -> Self {
+        Self {
+            options,
+            selected,
+            on_change: Rc::new(on_change),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> From<Select<T>> for View {
+    fn from(select: Select<T>) -> Self {
+        let options = select.options;
+        let selected = select.selected;
+        let on_change = select.on_change;
+
+        move || {
+            {
+                let current = selected.get_clone();
+                let label = options
+                    .iter()
+                    .find(|(_, value)| *value == current)
+                    .map(|(label, _)| label.clone())
+                    .unwrap_or_default();
+
+                let items = options
+                    .iter()
+                    .map(|(label, value)| {
+                        let on_change = on_change.clone();
+                        let value = value.clone();
+                        DropdownItem::new(label.clone(), move || on_change(value.clone()))
+                            .selected(current == value)
+                    })
+                    .collect();
+
+                DropdownMenu::new(
+                    span()
+                        .class(tw!(FontSize::Sm))
+                        .children(format!("{label} ▾")),
+                    items,
+                )
+            }
+            .into()
+        }
+    }
+}
+
+/// Select of several `options` with a type-ahead filter, showing the selected
+/// ones as chips.
+///
+/// Only the visible options are rendered, so it stays fast with long lists.
+pub struct MultiSelect<T: Clone + PartialEq + 'static> {
+    options: Vec<(String, T)>,
+    selected: ReadSignal<Vec<T>>,
+    on_change: Rc<dyn Fn(Vec<T>)>,
+    placeholder: &'static str,
+}
+
+impl<T: Clone + PartialEq + 'static> MultiSelect<T> {
+    /// Height in pixels of each option, fixed to know which ones are visible.
+    const ROW_HEIGHT: f64 = 32.0;
+    const VISIBLE_ROWS: usize = 8;
+    /// Options rendered above and below the visible ones, so scrolling doesn't flicker.
+    const OVERSCAN: usize = 4;
+
+    pub fn new(
+        options: Vec<(String, T)>,
+        selected: ReadSignal<Vec<T>>,
+        on_change: impl Fn(Vec<T>) + 'static,
+    ) -> Self {
+        Self {
+            options,
+            selected,
+            on_change: Rc::new(on_change),
+            placeholder: "Search",
+        }
+    }
+
+    pub fn placeholder(mut self, placeholder: &'static str) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+}
+
+impl<T: Clone + PartialEq + 'static> From<MultiSelect<T>> for View {
+    fn from(select: MultiSelect<T>) -> Self {
+        let options = Rc::new(select.options);
+        let selected = select.selected;
+        let on_change = select.on_change;
+        let query = create_signal(String::new());
+        let open = create_signal(false);
+        let highlighted = create_signal(0usize);
+        let scroll_top = create_signal(0.0);
+        let list_ref = create_node_ref();
+        let theme = use_theme();
+
+        // Indexes of the options matching the query
+        let matches = create_memo({
+            let options = options.clone();
+            move || {
+                let query = query.with(|query| query.trim().to_lowercase());
+                options
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (label, _))| label.to_lowercase().contains(&query))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>()
+            }
+        });
+
+        let toggle = {
+            let options = options.clone();
+            let on_change = on_change.clone();
+            move |index: usize| {
+                let value = &options[index].1;
+                let mut values = selected.get_clone();
+                match values.iter().position(|v| v == value) {
+                    Some(position) => {
+                        values.remove(position);
+                    }
+                    None => values.push(value.clone()),
+                }
+                on_change(values);
+            }
+        };
+
+        // Starts from the top of the list whenever the query changes
+        create_effect(move || {
+            query.track();
+            highlighted.set(0);
+            scroll_top.set(0.0);
+            if let Some(list) = list_ref.try_get() {
+                list.unchecked_into::<HtmlElement>().set_scroll_top(0);
+            }
+        });
+
+        let scroll_to_highlighted = move || {
+            let Some(list) = list_ref.try_get() else {
+                return;
+            };
+            let list: HtmlElement = list.unchecked_into();
+            let top = highlighted.get() as f64 * MultiSelect::<T>::ROW_HEIGHT;
+            let height = MultiSelect::<T>::VISIBLE_ROWS as f64 * MultiSelect::<T>::ROW_HEIGHT;
+            let scroll = list.scroll_top() as f64;
+
+            if top < scroll {
+                list.set_scroll_top(top as i32);
+            } else if top + MultiSelect::<T>::ROW_HEIGHT > scroll + height {
+                list.set_scroll_top((top + MultiSelect::<T>::ROW_HEIGHT - height) as i32);
+            }
+        };
+
+        let on_keydown = {
+            let toggle = toggle.clone();
+            move |event: KeyboardEvent| {
+                let count = matches.with(Vec::len);
+                match event.key().as_str() {
+                    "ArrowDown" if count > 0 => {
+                        open.set(true);
+                        highlighted.set((highlighted.get() + 1) % count);
+                        scroll_to_highlighted();
+                    }
+                    "ArrowUp" if count > 0 => {
+                        open.set(true);
+                        highlighted.set((highlighted.get() + count - 1) % count);
+                        scroll_to_highlighted();
+                    }
+                    "Enter" if open.get() && count > 0 => {
+                        toggle(matches.with(|matches| matches[highlighted.get().min(count - 1)]))
+                    }
+                    "Escape" if open.get() => open.set(false),
+                    // Removes the last chip, like deleting the text before the cursor
+                    "Backspace" if query.with(String::is_empty) => {
+                        let mut values = selected.get_clone();
+                        if values.pop().is_none() {
+                            return;
+                        }
+                        on_change(values);
+                    }
+                    _ => return,
+                }
+
+                event.prevent_default();
+                event.stop_propagation();
+            }
+        };
+
+        let chips = {
+            let options = options.clone();
+            let toggle = toggle.clone();
+            move || {
+                selected
+                    .get_clone()
+                    .into_iter()
+                    .filter_map(|value| options.iter().position(|(_, v)| *v == value))
+                    .map(|index| {
+                        let toggle = toggle.clone();
+                        button()
+                            .class(tw!(
+                                Padding::Px3,
+                                Padding::Py1,
+                                BorderRadius::Full,
+                                FontSize::Sm,
+                                BackgroundColor::Red300,
+                                TextColor::Black
+                            ))
+                            .attr("aria-label", format!("Remove {}", options[index].0))
+                            .children(format!("{} ✕", options[index].0))
+                            .on(click, move |_| toggle(index))
+                            .into()
+                    })
+                    .collect::<Vec<View>>()
+            }
+        };
+
+        let rows = move || {
+            let matches = matches.get_clone();
+            if matches.is_empty() {
+                return p()
+                    .class(tw!(
+                        Padding::Px3,
+                        Padding::Py1_5,
+                        FontSize::Sm,
+                        TextColor::Gray500
+                    ))
+                    .children("No matches")
+                    .into();
+            }
+
+            let row_height = MultiSelect::<T>::ROW_HEIGHT;
+            let first = (scroll_top.get() / row_height) as usize;
+            let start = first.saturating_sub(MultiSelect::<T>::OVERSCAN);
+            let end = (first + MultiSelect::<T>::VISIBLE_ROWS + MultiSelect::<T>::OVERSCAN)
+                .min(matches.len());
+
+            let visible = (start..end)
+                .map(|position| {
+                    let index = matches[position];
+                    let toggle = toggle.clone();
+                    let value = options[index].1.clone();
+                    let is_selected = move || selected.with(|values| values.contains(&value));
+
+                    button()
+                        .class(move || {
+                            let background = match (is_selected(), highlighted.get() == position) {
+                                (true, _) => BackgroundColor::Red300,
+                                (false, true) => theme.get().surface(),
+                                (false, false) => BackgroundColor::Transparent,
+                            };
+                            format!(
+                                "{} {}",
+                                tw!(
+                                    Position::Absolute,
+                                    Width::Full,
+                                    Padding::Px3,
+                                    BorderRadius::Md,
+                                    TextAlign::Left,
+                                    FontSize::Sm
+                                ),
+                                background.as_class()
+                            )
+                        })
+                        .style(format!(
+                            "top: {}px; height: {row_height}px",
+                            position as f64 * row_height
+                        ))
+                        .children(options[index].0.clone())
+                        .on(mouseenter, move |_| highlighted.set(position))
+                        .on(click, move |_| toggle(index))
+                        .into()
+                })
+                .collect::<Vec<View>>();
+
+            div()
+                .class(tw!(Position::Relative))
+                .style(format!("height: {}px", matches.len() as f64 * row_height))
+                .children(visible)
+                .into()
+        };
+
+        let list = move || {
+            if !open.get() {
+                return View::default();
+            }
+
+            (
+                div()
+                    .class(tw!(
+                        Position::Fixed,
+                        TopRightBottomLeft::Inset0,
+                        ZIndex::_10
+                    ))
+                    .on(click, move |_| open.set(false)),
+                div()
+                    .r#ref(list_ref)
+                    .class(move || {
+                        format!(
+                            "{} {}",
+                            tw!(
+                                Position::Absolute,
+                                TopRightBottomLeft::TopFull,
+                                TopRightBottomLeft::Left0,
+                                ZIndex::_20,
+                                Width::Full,
+                                Padding::P1,
+                                BorderRadius::Lg,
+                                BoxShadow::Lg,
+                                Overflow::YAuto
+                            ),
+                            theme.get().page()
+                        )
+                    })
+                    .style(format!(
+                        "max-height: {}px",
+                        MultiSelect::<T>::VISIBLE_ROWS as f64 * MultiSelect::<T>::ROW_HEIGHT
+                    ))
+                    .on(scroll, move |event: Event| {
+                        let list: Element = event.target().unwrap_throw().unchecked_into();
+                        scroll_top.set(list.scroll_top() as f64);
+                    })
+                    .children(rows.clone()),
+            )
+                .into()
+        };
+
+        div()
+            .class(tw!(
+                Position::Relative,
+                Display::Flex,
+                FlexDirection::Col,
+                Gap::_2
+            ))
+            .on(keydown, on_keydown)
+            .children(
+                div()
+                    .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_2))
+                    .children(chips),
+            )
+            .children(
+                input()
+                    .class(move || {
+                        format!(
+                            "{} {}",
+                            tw!(Width::Full, Padding::Px3, Padding::Py1_5, BorderRadius::Lg),
+                            theme.get().control()
+                        )
+                    })
+                    .r#type("search")
+                    .placeholder(select.placeholder)
+                    .bind(bind::value, query)
+                    .on(focus, move |_| open.set(true))
+                    .on(input_event, move |_| open.set(true)),
+            )
+            .children(list)
+            .into()
+    }
+}
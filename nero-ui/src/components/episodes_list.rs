@@ -0,0 +1,178 @@
+use std::rc::Rc;
+
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    flexbox_grid::{AlignItems, FlexDirection, Gap},
+    layout::Display,
+    sizing::Width,
+    spacing::Padding,
+    typography::{FontSize, TextColor},
+};
+use sycamore::{
+    prelude::HtmlInputAttributes,
+    web::{
+        events::{change, click, Event},
+        tags::{button, div, input, li, section},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::HtmlInputElement;
+
+use crate::{
+    hooks::{persisted_signal, use_theme},
+    storage::HistoryEntry,
+    tw,
+    types::Episode,
+    utils::ViewBuilder,
+};
+
+use super::{Button, Icon, IconType, IntoCard, IntoSmallCard, List, ListHeader};
+
+/// Episodes of a series with controls to sort them, hide the watched ones and
+/// jump to an episode number.
+pub struct EpisodesList {
+    episodes: Rc<Vec<Episode>>,
+    small: bool,
+    on_select: Option<Rc<dyn Fn(u16)>>,
+}
+
+impl EpisodesList {
+    pub fn new(episodes: Vec<Episode>) -> Self {
+        Self {
+            episodes: Rc::new(episodes),
+            small: false,
+            on_select: None,
+        }
+    }
+
+    /// Renders the episodes as small cards, e.g. next to the player.
+    pub fn small(mut self, small: bool) -> Self {
+        self.small = small;
+        self
+    }
+
+    /// Called with the number of the clicked episode.
+    pub fn on_select(mut self, on_select: impl Fn(u16) + 'static) -> Self {
+        self.on_select = Some(Rc::new(on_select));
+        self
+    }
+
+    fn element_id(number: u16) -> String {
+        format!("episode-{number}")
+    }
+
+    /// Scrolls the list to the episode `number`, if it's shown.
+    fn jump_to(number: u16) {
+        let element = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(&Self::element_id(number)));
+
+        if let Some(element) = element {
+            element.scroll_into_view();
+        }
+    }
+
+    fn is_watched(episode: &Episode) -> bool {
+        episode
+            .progress
+            .is_some_and(|progress| progress >= HistoryEntry::FINISHED_THRESHOLD)
+    }
+}
+
+impl From<EpisodesList> for View {
+    fn from(list: EpisodesList) -> Self {
+        let descending = persisted_signal("nero.episodes.descending", false);
+        let unwatched_only = persisted_signal("nero.episodes.unwatched_only", false);
+        let episodes = list.episodes;
+        let small = list.small;
+        let on_select = list.on_select;
+        let theme = use_theme();
+
+        let controls = div()
+            .class(tw!(Display::Flex, AlignItems::Center, Gap::_2))
+            .children(
+                input()
+                    .class(move || {
+                        format!(
+                            "{} {}",
+                            tw!(
+                                Width::_20,
+                                Padding::Px2,
+                                Padding::Py1,
+                                BorderRadius::Lg,
+                                FontSize::Sm
+                            ),
+                            theme.get().control()
+                        )
+                    })
+                    .r#type("number")
+                    .min("1")
+                    .placeholder("Go to")
+                    .on(change, |event: Event| {
+                        let input: HtmlInputElement =
+                            event.target().unwrap_throw().unchecked_into();
+                        if let Ok(number) = input.value().trim().parse() {
+                            EpisodesList::jump_to(number);
+                        }
+                    }),
+            )
+            .children(
+                button()
+                    .class(move || {
+                        let base =
+                            tw!(Padding::Px3, Padding::Py1, BorderRadius::Full, FontSize::Sm);
+                        let color = match unwatched_only.get() {
+                            true => tw!(BackgroundColor::Red300, TextColor::Black),
+                            false => theme.get().control(),
+                        };
+                        format!("{base} {color}")
+                    })
+                    .attr("aria-pressed", move || unwatched_only.get().to_string())
+                    .children("Unwatched")
+                    .on(click, move |_| unwatched_only.set(!unwatched_only.get())),
+            )
+            .children(Button::icon(Icon::new(IconType::Sort), move |_| {
+                descending.set(!descending.get())
+            }));
+
+        let items = move || {
+            let mut shown = episodes
+                .iter()
+                .filter(|episode| !unwatched_only.get() || !EpisodesList::is_watched(episode))
+                .cloned()
+                .collect::<Vec<_>>();
+            shown.sort_by_key(|episode| episode.number);
+            if descending.get() {
+                shown.reverse();
+            }
+
+            let items = shown
+                .into_iter()
+                .map(|episode| {
+                    let number = episode.number;
+                    let card: View = match small {
+                        true => episode.into_small_card().into(),
+                        false => episode.into_card().into(),
+                    };
+
+                    li().id(EpisodesList::element_id(number))
+                        .when_some(on_select.clone(), |this, on_select| {
+                            this.on(click, move |_| on_select(number))
+                        })
+                        .children(card)
+                        .into()
+                })
+                .collect::<Vec<View>>();
+
+            List::new(items)
+        };
+
+        section()
+            .class(tw!(Display::Flex, FlexDirection::Col))
+            .children(ListHeader::new("Episodes").end_slot(controls))
+            .children(items)
+            .into()
+    }
+}
@@ -0,0 +1,57 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    effects::BoxShadow,
+    flexbox_grid::JustifyContent,
+    interactivity::PointerEvents,
+    layout::{Display, Position, TopRightBottomLeft, ZIndex},
+    spacing::Padding,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::web::{
+    tags::{div, span},
+    GlobalProps, HtmlGlobalAttributes, View,
+};
+
+use crate::{hooks::use_feedback, tw};
+
+/// Visual alternative to the feedback chimes, briefly showing a caption of the
+/// event at the top of the page.
+pub struct FeedbackFlash;
+
+impl From<FeedbackFlash> for View {
+    fn from(_: FeedbackFlash) -> Self {
+        let flashing = use_feedback().flashing();
+
+        div()
+            .class(tw!(
+                Position::Fixed,
+                TopRightBottomLeft::Top4,
+                TopRightBottomLeft::Left0,
+                TopRightBottomLeft::Right0,
+                ZIndex::_50,
+                Display::Flex,
+                JustifyContent::Center,
+                PointerEvents::None
+            ))
+            .attr("role", "status")
+            .attr("aria-live", "polite")
+            .children(move || match flashing.get() {
+                Some(event) => span()
+                    .class(tw!(
+                        Padding::Px4,
+                        Padding::Py2,
+                        BorderRadius::Full,
+                        BoxShadow::Lg,
+                        FontSize::Sm,
+                        FontWeight::Semibold,
+                        BackgroundColor::Red300,
+                        TextColor::Black
+                    ))
+                    .children(event.caption())
+                    .into(),
+                None => View::default(),
+            })
+            .into()
+    }
+}
@@ -0,0 +1,156 @@
+use std::rc::Rc;
+
+use js_sys::Array;
+use sycamore::{
+    prelude::{
+        create_node_ref, create_signal, on_cleanup, on_mount, HtmlImgAttributes, NodeRef, Signal,
+    },
+    web::{events::error, tags::img, GlobalAttributes, GlobalProps, HtmlGlobalAttributes, View},
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+use crate::{hooks::use_settings, image_cache};
+
+/// Shown when there is no image or it fails to load.
+const PLACEHOLDER: &str = "data:image/svg+xml,\
+    %3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 24 24'%3E\
+    %3Crect width='24' height='24' fill='%23e5e7eb'/%3E\
+    %3Cpath d='M6 17l4-5 3 4 2-2 3 3z' fill='%239ca3af'/%3E\
+    %3Ccircle cx='15.5' cy='8.5' r='1.5' fill='%239ca3af'/%3E\
+    %3C/svg%3E";
+
+/// Lazily loaded image falling back to a placeholder.
+pub struct Image {
+    src: Option<String>,
+    alt: String,
+    class: &'static str,
+    cached: bool,
+}
+
+impl Image {
+    pub fn new(src: Option<String>, alt: impl Into<String>) -> Self {
+        Self {
+            src,
+            alt: alt.into(),
+            class: "",
+            cached: false,
+        }
+    }
+
+    pub fn class(mut self, class: &'static str) -> Self {
+        self.class = class;
+        self
+    }
+
+    /// Keeps the image in the browser cache, for lists loading the same posters often.
+    pub fn cached(mut self, cached: bool) -> Self {
+        self.cached = cached;
+        self
+    }
+
+    /// Points `src` to the cached copy of `url`, or to `url` itself when it can't
+    /// be cached.
+    fn load(
+        url: String,
+        max_entries: u32,
+        src: Signal<String>,
+        object_url: Signal<Option<String>>,
+    ) {
+        spawn_local(async move {
+            let resolved = image_cache::object_url(&url, max_entries, src).await;
+            // The image may have been removed while loading
+            if !src.is_alive() {
+                if let Ok(resolved) = resolved {
+                    image_cache::revoke(&resolved);
+                }
+                return;
+            }
+
+            match resolved {
+                Ok(resolved) => {
+                    object_url.set(Some(resolved.clone()));
+                    src.set(resolved);
+                }
+                // Images served without CORS can't be cached, but can still be shown
+                Err(_) => src.set(url),
+            }
+        });
+    }
+
+    /// Runs `on_visible` once the element of `node_ref` gets near the viewport, so
+    /// images further down a list aren't fetched until scrolled to.
+    fn when_visible(node_ref: NodeRef, on_visible: Rc<dyn Fn()>) {
+        on_mount(move || {
+            let element: Element = node_ref.get().unchecked_into();
+            let callback = Closure::<dyn FnMut(Array, IntersectionObserver)>::new({
+                let on_visible = on_visible.clone();
+                move |entries: Array, observer: IntersectionObserver| {
+                    let visible = entries.iter().any(|entry| {
+                        entry
+                            .unchecked_into::<IntersectionObserverEntry>()
+                            .is_intersecting()
+                    });
+                    if visible {
+                        observer.disconnect();
+                        on_visible();
+                    }
+                }
+            });
+
+            let options = IntersectionObserverInit::new();
+            options.set_root_margin("200px");
+            match IntersectionObserver::new_with_options(
+                callback.as_ref().unchecked_ref(),
+                &options,
+            ) {
+                Ok(observer) => {
+                    observer.observe(&element);
+                    on_cleanup(move || {
+                        observer.disconnect();
+                        drop(callback);
+                    });
+                }
+                Err(_) => on_visible(),
+            }
+        });
+    }
+}
+
+impl From<Image> for View {
+    fn from(image: Image) -> Self {
+        let src = create_signal(match (&image.src, image.cached) {
+            (Some(url), false) => url.clone(),
+            _ => PLACEHOLDER.to_owned(),
+        });
+        let object_url = create_signal(None::<String>);
+        let max_entries = use_settings().with(|settings| settings.image_cache.max_entries());
+        let node_ref = create_node_ref();
+
+        if let (Some(url), true) = (image.src, image.cached) {
+            Image::when_visible(
+                node_ref,
+                Rc::new(move || Image::load(url.clone(), max_entries, src, object_url)),
+            );
+        }
+        on_cleanup(move || {
+            if let Some(url) = object_url.get_clone_untracked() {
+                image_cache::revoke(&url);
+            }
+        });
+
+        img()
+            .r#ref(node_ref)
+            .class(image.class)
+            .src(move || src.get_clone())
+            .alt(image.alt)
+            .attr("loading", "lazy")
+            .on(error, move |_| {
+                if src.with_untracked(|src| src != PLACEHOLDER) {
+                    src.set(PLACEHOLDER.to_owned());
+                }
+            })
+            .into()
+    }
+}
@@ -1,5 +1,4 @@
 use rustwind::{
-    backgrounds::BackgroundColor,
     flexbox_grid::{AlignItems, FlexDirection, JustifyContent},
     layout::{Display, Position, TopRightBottomLeft},
     sizing::Width,
@@ -11,7 +10,7 @@ use sycamore::web::{
     GlobalProps, HtmlGlobalAttributes, View,
 };
 
-use crate::{tw, utils::ViewBuilder};
+use crate::{hooks::use_theme, tw, utils::ViewBuilder};
 
 pub struct ListHeader {
     label: &'static str,
@@ -43,11 +42,14 @@ impl From<ListHeader> for View {
     fn from(list_header: ListHeader) -> Self {
         header()
             .when(list_header.sticky, |this| {
-                this.class(tw!(
-                    Position::Sticky,
-                    TopRightBottomLeft::Top0,
-                    BackgroundColor::White
-                ))
+                let theme = use_theme();
+                this.class(move || {
+                    format!(
+                        "{} {}",
+                        tw!(Position::Sticky, TopRightBottomLeft::Top0),
+                        theme.get().page()
+                    )
+                })
             })
             .children(
                 div()
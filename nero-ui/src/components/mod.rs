@@ -1,11 +1,41 @@
 pub mod button;
 pub mod card;
+pub mod countdown;
+pub mod dropdown;
+mod episodes_list;
+mod feedback_flash;
 pub mod icon;
+pub mod image;
 pub mod list;
+pub mod modal;
+pub mod nav_rail;
+pub mod player_controls;
+pub mod progress;
+mod rich_text;
+pub mod skeleton;
+pub mod subtitle_overlay;
+pub mod tabs;
+pub mod toast;
 pub mod toolbar;
+pub mod video_player;
 
 pub use button::*;
 pub use card::*;
+pub use countdown::*;
+pub use dropdown::*;
+pub use episodes_list::*;
+pub use feedback_flash::*;
 pub use icon::*;
+pub use image::*;
 pub use list::*;
+pub use modal::*;
+pub use nav_rail::*;
+pub use player_controls::*;
+pub use progress::*;
+pub use rich_text::*;
+pub use skeleton::*;
+pub use subtitle_overlay::*;
+pub use tabs::*;
+pub use toast::*;
 pub use toolbar::*;
+pub use video_player::*;
@@ -0,0 +1,214 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    effects::{BoxShadow, Opacity},
+    flexbox_grid::{AlignItems, FlexDirection, Gap, JustifyContent},
+    layout::{Display, Position, TopRightBottomLeft, ZIndex},
+    sizing::Width,
+    spacing::Padding,
+    typography::{FontSize, FontWeight},
+};
+use sycamore::{
+    prelude::{create_effect, create_node_ref, on_mount, NodeRef, Signal},
+    web::{
+        events::{click, keydown, KeyboardEvent},
+        tags::{div, h2, header},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlElement;
+
+use crate::{hooks::use_theme, tw, utils::ViewBuilder};
+
+use super::{Button, Icon, IconType};
+
+/// Elements reachable with the Tab key.
+const FOCUSABLE: &str =
+    "button, [href], input, select, textarea, [tabindex]:not([tabindex=\"-1\"])";
+
+/// Dialog shown over the page while `open` is `true`.
+///
+/// It closes on Escape or when clicking outside of it, and keeps the focus
+/// inside until then, restoring it to the previously focused element.
+pub struct Modal {
+    open: Signal<bool>,
+    title: Option<&'static str>,
+    width: Width,
+    children: View,
+}
+
+impl Modal {
+    pub fn new(open: Signal<bool>, children: impl Into<View>) -> Self {
+        Self {
+            open,
+            title: None,
+            width: Width::_96,
+            children: children.into(),
+        }
+    }
+
+    pub fn title(mut self, title: &'static str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    /// Width of the dialog, `Width::_96` by default.
+    pub fn width(mut self, width: Width) -> Self {
+        self.width = width;
+        self
+    }
+
+    fn focusable(dialog: &HtmlElement) -> Vec<HtmlElement> {
+        let Ok(nodes) = dialog.query_selector_all(FOCUSABLE) else {
+            return Vec::new();
+        };
+
+        (0..nodes.length())
+            .filter_map(|index| nodes.get(index)?.dyn_into::<HtmlElement>().ok())
+            .collect()
+    }
+
+    /// Wraps the focus around the first and last focusable elements of the dialog.
+    fn trap_focus(dialog: &HtmlElement, event: &KeyboardEvent) {
+        let focusable = Self::focusable(dialog);
+        let (Some(first), Some(last)) = (focusable.first(), focusable.last()) else {
+            event.prevent_default();
+            return;
+        };
+        let active = dialog
+            .owner_document()
+            .and_then(|document| document.active_element())
+            .and_then(|element| element.dyn_into::<HtmlElement>().ok());
+
+        let target = match event.shift_key() {
+            true if active.as_ref() == Some(first) || active.as_ref() == Some(dialog) => last,
+            false if active.as_ref() == Some(last) => first,
+            _ => return,
+        };
+        event.prevent_default();
+        let _ = target.focus();
+    }
+
+    fn watch_focus(open: Signal<bool>, dialog_ref: NodeRef) {
+        let mut previous = None::<HtmlElement>;
+
+        on_mount(move || {
+            create_effect(move || {
+                let dialog: HtmlElement = dialog_ref.get().unchecked_into();
+
+                match open.get() {
+                    true => {
+                        previous = dialog
+                            .owner_document()
+                            .and_then(|document| document.active_element())
+                            .and_then(|element| element.dyn_into().ok());
+
+                        // Deferred until the dialog is displayed, hidden elements can't be focused
+                        spawn_local(async move {
+                            let target = Self::focusable(&dialog).into_iter().next();
+                            let _ = target.as_ref().unwrap_or(&dialog).focus();
+                        });
+                    }
+                    false => {
+                        if let Some(previous) = previous.take() {
+                            let _ = previous.focus();
+                        }
+                    }
+                }
+            });
+        });
+    }
+}
+
+impl From<Modal> for View {
+    fn from(modal: Modal) -> Self {
+        let open = modal.open;
+        let dialog_ref = create_node_ref();
+        let theme = use_theme();
+        let width = modal.width.as_class();
+
+        Modal::watch_focus(open, dialog_ref);
+
+        div()
+            .class(move || {
+                let display = match open.get() {
+                    true => Display::Flex,
+                    false => Display::Hidden,
+                };
+                format!(
+                    "{} {}",
+                    tw!(
+                        Position::Fixed,
+                        TopRightBottomLeft::Inset0,
+                        ZIndex::_50,
+                        AlignItems::Center,
+                        JustifyContent::Center
+                    ),
+                    display.as_class()
+                )
+            })
+            .children(
+                div()
+                    .class(tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Inset0,
+                        BackgroundColor::Black,
+                        Opacity::_50
+                    ))
+                    .on(click, move |_| open.set(false)),
+            )
+            .children(
+                div()
+                    .r#ref(dialog_ref)
+                    .attr("role", "dialog")
+                    .attr("aria-modal", "true")
+                    .attr("tabindex", "-1")
+                    .class(move || {
+                        format!(
+                            "{} {width} {}",
+                            tw!(
+                                Position::Relative,
+                                Display::Flex,
+                                FlexDirection::Col,
+                                Gap::_4,
+                                Padding::P6,
+                                BorderRadius::Xl,
+                                BoxShadow::Lg
+                            ),
+                            theme.get().page()
+                        )
+                    })
+                    .on(keydown, move |event: KeyboardEvent| {
+                        // Page shortcuts must not act behind the dialog
+                        event.stop_propagation();
+
+                        match event.key().as_str() {
+                            "Escape" => open.set(false),
+                            "Tab" => Modal::trap_focus(&dialog_ref.get().unchecked_into(), &event),
+                            _ => {}
+                        }
+                    })
+                    .when_some(modal.title, |this, title| {
+                        this.children(
+                            header()
+                                .class(tw!(
+                                    Display::Flex,
+                                    AlignItems::Center,
+                                    JustifyContent::Between
+                                ))
+                                .children(
+                                    h2().class(tw!(FontSize::Xl, FontWeight::Semibold))
+                                        .children(title),
+                                )
+                                .children(Button::icon(Icon::new(IconType::Close), move |_| {
+                                    open.set(false)
+                                })),
+                        )
+                    })
+                    .children(modal.children),
+            )
+            .into()
+    }
+}
@@ -0,0 +1,84 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    flexbox_grid::{AlignItems, FlexDirection, Gap},
+    layout::Display,
+    spacing::Padding,
+    transitions_animation::TransitionDuration,
+    typography::FontSize,
+};
+use sycamore::{
+    prelude::{HtmlAAttributes, ReadSignal},
+    web::{
+        tags::{a, nav, span},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+
+use crate::{hooks::use_theme, routes::AppRoutes, tw};
+
+use super::{Icon, IconType};
+
+/// Links to the main pages, highlighting the one of the current route.
+pub struct NavRail {
+    route: ReadSignal<AppRoutes>,
+}
+
+impl NavRail {
+    const ITEMS: [(AppRoutes, IconType, &'static str); 6] = [
+        (AppRoutes::Home, IconType::Home, "Home"),
+        (AppRoutes::Search, IconType::Search, "Search"),
+        (AppRoutes::Library, IconType::Bookmark, "Library"),
+        (AppRoutes::History, IconType::History, "History"),
+        (AppRoutes::Extensions, IconType::Extensions, "Extensions"),
+        (AppRoutes::Settings, IconType::Settings, "Settings"),
+    ];
+
+    pub fn new(route: ReadSignal<AppRoutes>) -> Self {
+        Self { route }
+    }
+}
+
+impl From<NavRail> for View {
+    fn from(rail: NavRail) -> Self {
+        let route = rail.route;
+        let theme = use_theme();
+
+        let links = NavRail::ITEMS
+            .into_iter()
+            .map(|(target, icon, label)| {
+                a().href(target.path())
+                    .attr("aria-label", label)
+                    .class(move || {
+                        let base = tw!(
+                            Display::Flex,
+                            FlexDirection::Col,
+                            AlignItems::Center,
+                            Gap::_1,
+                            Padding::P2,
+                            BorderRadius::Lg,
+                            FontSize::Xs,
+                            TransitionDuration::_300
+                        );
+                        match route.get() == target {
+                            true => format!("{base} {}", BackgroundColor::Red300.as_class()),
+                            false => format!("{base} {}", theme.get().hover()),
+                        }
+                    })
+                    .children(Icon::new(icon))
+                    .children(span().children(label))
+                    .into()
+            })
+            .collect::<Vec<View>>();
+
+        nav()
+            .class(tw!(
+                Display::Flex,
+                FlexDirection::Col,
+                Gap::_2,
+                Padding::Py4
+            ))
+            .children(links)
+            .into()
+    }
+}
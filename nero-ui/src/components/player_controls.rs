@@ -0,0 +1,553 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    effects::Opacity,
+    flexbox_grid::{AlignItems, FlexDirection, Gap, JustifyContent},
+    interactivity::Cursor,
+    layout::{Display, Position, TopRightBottomLeft},
+    sizing::{Height, Width},
+    spacing::Padding,
+    svg::Fill,
+    transitions_animation::TransitionDuration,
+    typography::{FontSize, TextColor},
+};
+use sycamore::{
+    prelude::{
+        create_effect, create_memo, create_node_ref, create_signal, on_cleanup, on_mount,
+        HtmlInputAttributes, NodeRef, ReadSignal, Signal,
+    },
+    web::{
+        events::{click, input as input_event, mouseleave, mousemove, Event, MouseEvent},
+        tags::{div, input, span},
+        GlobalAttributes, GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{closure::Closure, JsCast, UnwrapThrowExt};
+use web_sys::{HtmlInputElement, HtmlVideoElement};
+
+use crate::{format, tw, utils::ViewBuilder};
+
+use super::{
+    AnimatedIcon, Button, ButtonSize, ButtonVariant, DropdownItem, DropdownMenu, IconState,
+};
+
+/// Events after which the state of a video may have changed.
+const MEDIA_EVENTS: [&str; 9] = [
+    "play",
+    "pause",
+    "timeupdate",
+    "durationchange",
+    "loadedmetadata",
+    "progress",
+    "volumechange",
+    "ratechange",
+    "emptied",
+];
+
+/// Playback state of a video element, updated from its media events.
+#[derive(Clone, Copy)]
+pub struct MediaState {
+    pub playing: ReadSignal<bool>,
+    /// Current position in seconds.
+    pub time: ReadSignal<f64>,
+    /// Length of the video in seconds, `0.0` until it's known.
+    pub duration: ReadSignal<f64>,
+    /// Downloaded `(start, end)` ranges in seconds.
+    pub buffered: ReadSignal<Vec<(f64, f64)>>,
+    pub volume: ReadSignal<f64>,
+    pub muted: ReadSignal<bool>,
+    pub rate: ReadSignal<f64>,
+}
+
+impl MediaState {
+    pub fn watch(video_ref: NodeRef) -> Self {
+        let playing = create_signal(false);
+        let time = create_signal(0.0);
+        let duration = create_signal(0.0);
+        let buffered = create_signal(Vec::new());
+        let volume = create_signal(1.0);
+        let muted = create_signal(false);
+        let rate = create_signal(1.0);
+
+        on_mount(move || {
+            let video: HtmlVideoElement = video_ref.get().unchecked_into();
+
+            let listener = Closure::<dyn FnMut()>::new({
+                let video = video.clone();
+                move || {
+                    let ranges = video.buffered();
+                    let ranges = (0..ranges.length())
+                        .filter_map(|index| {
+                            Some((ranges.start(index).ok()?, ranges.end(index).ok()?))
+                        })
+                        .collect::<Vec<_>>();
+                    let length = video.duration();
+
+                    // Most events only change one of them, avoids updating the others
+                    set_changed(playing, !video.paused());
+                    set_changed(time, video.current_time());
+                    set_changed(duration, if length.is_finite() { length } else { 0.0 });
+                    set_changed(buffered, ranges);
+                    set_changed(volume, video.volume());
+                    set_changed(muted, video.muted());
+                    set_changed(rate, video.playback_rate());
+                }
+            });
+            for event in MEDIA_EVENTS {
+                let _ = video
+                    .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+            }
+
+            on_cleanup(move || {
+                for event in MEDIA_EVENTS {
+                    let _ = video.remove_event_listener_with_callback(
+                        event,
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+            });
+        });
+
+        Self {
+            playing: *playing,
+            time: *time,
+            duration: *duration,
+            buffered: *buffered,
+            volume: *volume,
+            muted: *muted,
+            rate: *rate,
+        }
+    }
+}
+
+fn set_changed<T: PartialEq>(signal: Signal<T>, value: T) {
+    if signal.with_untracked(|current| *current != value) {
+        signal.set(value);
+    }
+}
+
+/// Seek bar showing the played and buffered parts of a video.
+pub struct Scrubber {
+    time: ReadSignal<f64>,
+    duration: ReadSignal<f64>,
+    buffered: ReadSignal<Vec<(f64, f64)>>,
+    on_seek: Box<dyn Fn(f64)>,
+}
+
+impl Scrubber {
+    pub fn new(state: MediaState, on_seek: impl Fn(f64) + 'static) -> Self {
+        Self {
+            time: state.time,
+            duration: state.duration,
+            buffered: state.buffered,
+            on_seek: Box::new(on_seek),
+        }
+    }
+}
+
+impl From<Scrubber> for View {
+    fn from(scrubber: Scrubber) -> Self {
+        let time = scrubber.time;
+        let duration = scrubber.duration;
+        let buffered = scrubber.buffered;
+        let on_seek = scrubber.on_seek;
+        let input_ref = create_node_ref();
+
+        // The position is set as a property, the attribute only holds the initial value
+        on_mount(move || {
+            create_effect(move || {
+                let input: HtmlInputElement = input_ref.get().unchecked_into();
+                input.set_max(&duration.get().to_string());
+                input.set_value_as_number(time.get());
+            });
+        });
+
+        let percent = move |seconds: f64| match duration.get() {
+            duration if duration > 0.0 => (seconds / duration * 100.0).clamp(0.0, 100.0),
+            _ => 0.0,
+        };
+
+        let ranges = move || {
+            buffered
+                .get_clone()
+                .into_iter()
+                .map(|(start, end)| {
+                    div()
+                        .class(tw!(
+                            Position::Absolute,
+                            Height::Full,
+                            BackgroundColor::Gray300
+                        ))
+                        .style(format!(
+                            "left: {}%; width: {}%",
+                            percent(start),
+                            percent(end - start)
+                        ))
+                        .into()
+                })
+                .collect::<Vec<View>>()
+        };
+
+        div()
+            .class(tw!(
+                Position::Relative,
+                Display::Flex,
+                AlignItems::Center,
+                Width::Full,
+                Height::_4
+            ))
+            .children(
+                div()
+                    .class(tw!(
+                        Position::Relative,
+                        Width::Full,
+                        Height::_1,
+                        BorderRadius::Full,
+                        BackgroundColor::Gray500
+                    ))
+                    .children(ranges)
+                    .children(
+                        div()
+                            .class(tw!(
+                                Position::Absolute,
+                                Height::Full,
+                                BackgroundColor::Red500
+                            ))
+                            .style(move || format!("width: {}%", percent(time.get()))),
+                    ),
+            )
+            .children(
+                input()
+                    .r#ref(input_ref)
+                    .class(tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Inset0,
+                        Width::Full,
+                        Opacity::_0,
+                        Cursor::Pointer
+                    ))
+                    .r#type("range")
+                    .min("0")
+                    .step("any")
+                    .attr("aria-label", "Seek")
+                    .on(input_event, move |event: Event| {
+                        let input: HtmlInputElement =
+                            event.target().unwrap_throw().unchecked_into();
+                        on_seek(input.value_as_number());
+                    }),
+            )
+            .into()
+    }
+}
+
+/// Mute toggle next to a volume slider.
+pub struct VolumeSlider {
+    volume: ReadSignal<f64>,
+    muted: ReadSignal<bool>,
+    on_change: Box<dyn Fn(f64)>,
+    on_mute: Box<dyn FnMut(MouseEvent)>,
+}
+
+impl VolumeSlider {
+    /// Creates a slider calling `on_change` with volumes between `0.0` and `1.0`,
+    /// and `on_mute` when the mute button is clicked.
+    pub fn new(
+        state: MediaState,
+        on_change: impl Fn(f64) + 'static,
+        on_mute: impl FnMut(MouseEvent) + 'static,
+    ) -> Self {
+        Self {
+            volume: state.volume,
+            muted: state.muted,
+            on_change: Box::new(on_change),
+            on_mute: Box::new(on_mute),
+        }
+    }
+}
+
+impl From<VolumeSlider> for View {
+    fn from(slider: VolumeSlider) -> Self {
+        let volume = slider.volume;
+        let muted = slider.muted;
+        let on_change = slider.on_change;
+        let silent = create_memo(move || muted.get() || volume.get() == 0.0);
+        let input_ref = create_node_ref();
+
+        on_mount(move || {
+            create_effect(move || {
+                let input: HtmlInputElement = input_ref.get().unchecked_into();
+                input.set_value_as_number(match silent.get() {
+                    true => 0.0,
+                    false => volume.get(),
+                });
+            });
+        });
+
+        div()
+            .class(tw!(Display::Flex, AlignItems::Center, Gap::_1))
+            .children(Button::icon(
+                AnimatedIcon::new(IconState::mute(*silent)).fill(Fill::White),
+                slider.on_mute,
+            ))
+            .children(
+                input()
+                    .r#ref(input_ref)
+                    .class(tw!(Width::_20, Cursor::Pointer))
+                    .r#type("range")
+                    .min("0")
+                    .max("1")
+                    .step("0.05")
+                    .attr("aria-label", "Volume")
+                    .on(input_event, move |event: Event| {
+                        let input: HtmlInputElement =
+                            event.target().unwrap_throw().unchecked_into();
+                        on_change(input.value_as_number());
+                    }),
+            )
+            .into()
+    }
+}
+
+/// Part of the video repeated by the A-B loop.
+#[derive(Clone, Copy, PartialEq)]
+enum LoopRange {
+    Off,
+    /// Start picked, waiting for the end.
+    Start(f64),
+    /// Start and end in seconds.
+    Range(f64, f64),
+}
+
+impl LoopRange {
+    /// Moves to the next step from the position at `time`: picking the start, the end,
+    /// then turning the loop off.
+    fn next(self, time: f64) -> Self {
+        match self {
+            LoopRange::Off => LoopRange::Start(time),
+            LoopRange::Start(start) if time > start => LoopRange::Range(start, time),
+            // An end before the start restarts the selection from there
+            LoopRange::Start(_) => LoopRange::Start(time),
+            LoopRange::Range(..) => LoopRange::Off,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            LoopRange::Off => "A-B".to_owned(),
+            LoopRange::Start(start) => format!("{} - …", format::clock(start)),
+            LoopRange::Range(start, end) => {
+                format!("{} - {}", format::clock(start), format::clock(end))
+            }
+        }
+    }
+}
+
+/// Controls shown over a video in place of the native ones, hidden while the
+/// video plays and the mouse doesn't move.
+pub struct PlayerControls {
+    video_ref: NodeRef,
+    trailing: Option<View>,
+}
+
+impl PlayerControls {
+    /// Milliseconds without moving the mouse before the controls hide.
+    const IDLE_DELAY: i32 = 3000;
+    const SPEEDS: [f64; 7] = [0.5, 0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+
+    pub fn new(video_ref: NodeRef) -> Self {
+        Self {
+            video_ref,
+            trailing: None,
+        }
+    }
+
+    /// Adds buttons at the end of the bar, e.g. to change how the video is displayed.
+    pub fn trailing(mut self, trailing: impl Into<View>) -> Self {
+        self.trailing = Some(trailing.into());
+        self
+    }
+
+    fn speed_label(rate: f64) -> String {
+        match rate == 1.0 {
+            true => "Normal".to_owned(),
+            false => format!("{rate}×"),
+        }
+    }
+
+    fn speed_menu(
+        state: MediaState,
+        video: impl Fn() -> HtmlVideoElement + Copy + 'static,
+    ) -> View {
+        let rate = state.rate.get();
+        let items = Self::SPEEDS
+            .map(|speed| {
+                DropdownItem::new(Self::speed_label(speed), move || {
+                    video().set_playback_rate(speed)
+                })
+                .selected(speed == rate)
+            })
+            .to_vec();
+
+        DropdownMenu::new(
+            span()
+                .class(tw!(FontSize::Sm, TextColor::White))
+                .children(format!("{} ▾", Self::speed_label(rate))),
+            items,
+        )
+        .upward()
+        .into()
+    }
+}
+
+impl From<PlayerControls> for View {
+    fn from(controls: PlayerControls) -> Self {
+        let video_ref = controls.video_ref;
+        let state = MediaState::watch(video_ref);
+        let video = move || video_ref.get().unchecked_into::<HtmlVideoElement>();
+
+        let active = create_signal(true);
+        let idle_timer = create_signal(0_u32);
+        let visible = create_memo(move || active.get() || !state.playing.get());
+
+        let wake = move |_: MouseEvent| {
+            active.set(true);
+            let generation = idle_timer.get() + 1;
+            idle_timer.set(generation);
+
+            if let Some(window) = web_sys::window() {
+                let hide = Closure::once_into_js(move || {
+                    // Moving the mouse again restarts the delay
+                    if idle_timer.is_alive() && idle_timer.get() == generation {
+                        active.set(false);
+                    }
+                });
+                let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                    hide.unchecked_ref(),
+                    PlayerControls::IDLE_DELAY,
+                );
+            }
+        };
+
+        let play = move |_: MouseEvent| match video().paused() {
+            true => {
+                let _ = video().play();
+            }
+            false => {
+                let _ = video().pause();
+            }
+        };
+
+        let loop_range = create_signal(LoopRange::Off);
+        create_effect(move || {
+            if let LoopRange::Range(start, end) = loop_range.get() {
+                if state.time.get() >= end {
+                    video().set_current_time(start);
+                }
+            }
+        });
+
+        let loop_button = Button::new(
+            span().children(move || loop_range.get().label()),
+            move |_| loop_range.set(loop_range.get().next(state.time.get_untracked())),
+        )
+        .size(ButtonSize::Small)
+        .variant(ButtonVariant::Ghost);
+
+        let time_label = move || {
+            format!(
+                "{} / {}",
+                format::clock(state.time.get()),
+                format::clock(state.duration.get())
+            )
+        };
+
+        let bar = div()
+            .class(tw!(
+                Display::Flex,
+                FlexDirection::Col,
+                Gap::_1,
+                Padding::Px3,
+                Padding::Py2
+            ))
+            .style("background: linear-gradient(transparent, rgb(0 0 0 / 0.6));")
+            // Clicks on the controls shouldn't also toggle playback
+            .on(click, |event: MouseEvent| event.stop_propagation())
+            .children(Scrubber::new(state, move |time| {
+                video().set_current_time(time)
+            }))
+            .children(
+                div()
+                    .class(tw!(
+                        Display::Flex,
+                        AlignItems::Center,
+                        JustifyContent::Between
+                    ))
+                    .children(
+                        div()
+                            .class(tw!(Display::Flex, AlignItems::Center, Gap::_2))
+                            .children(Button::icon(
+                                AnimatedIcon::new(IconState::play_pause(state.playing))
+                                    .fill(Fill::White),
+                                play,
+                            ))
+                            .children(VolumeSlider::new(
+                                state,
+                                move |volume| {
+                                    let video = video();
+                                    video.set_muted(false);
+                                    video.set_volume(volume);
+                                },
+                                move |_| {
+                                    let video = video();
+                                    video.set_muted(!video.muted());
+                                },
+                            ))
+                            .children(
+                                span()
+                                    .class(tw!(FontSize::Sm, TextColor::White))
+                                    .children(time_label),
+                            ),
+                    )
+                    .children(
+                        div()
+                            .class(tw!(Display::Flex, AlignItems::Center, Gap::_1))
+                            .children(
+                                span()
+                                    .class(move || match loop_range.get() {
+                                        LoopRange::Off => tw!(TextColor::White),
+                                        _ => tw!(TextColor::Red500),
+                                    })
+                                    .attr("title", "Repeat part of the video")
+                                    .children(loop_button),
+                            )
+                            .children(move || PlayerControls::speed_menu(state, video))
+                            .when_some(controls.trailing, |this, trailing| this.children(trailing)),
+                    ),
+            );
+
+        div()
+            .class(move || {
+                let visibility = match visible.get() {
+                    true => Opacity::_100,
+                    false => Opacity::_0,
+                };
+                format!(
+                    "{} {}",
+                    tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Inset0,
+                        Display::Flex,
+                        FlexDirection::Col,
+                        JustifyContent::End,
+                        TransitionDuration::_300
+                    ),
+                    visibility.as_class()
+                )
+            })
+            .on(mousemove, wake)
+            .on(mouseleave, move |_| active.set(false))
+            .on(click, play)
+            .children(bar)
+            .into()
+    }
+}
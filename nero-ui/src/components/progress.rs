@@ -0,0 +1,105 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    layout::Overflow,
+    sizing::{Height, Width},
+    typography::TextColor,
+};
+use sycamore::{
+    prelude::{create_memo, MaybeDyn},
+    web::{
+        tags::{circle, div, svg},
+        GlobalProps, HtmlGlobalAttributes, SvgGlobalAttributes, View,
+    },
+};
+
+use crate::tw;
+
+pub struct ProgressBar {
+    value: f64,
+}
+
+impl ProgressBar {
+    /// Creates a progress bar filled up to `value`, a fraction between `0.0` and `1.0`.
+    pub fn new(value: f64) -> Self {
+        Self {
+            value: value.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl From<ProgressBar> for View {
+    fn from(progress_bar: ProgressBar) -> Self {
+        div()
+            .class(tw!(
+                Width::Full,
+                Height::_1,
+                Overflow::Hidden,
+                BackgroundColor::Gray300
+            ))
+            .children(
+                div()
+                    .class(tw!(Height::Full, BackgroundColor::Red500))
+                    .style(format!("width: {}%", progress_bar.value * 100.0)),
+            )
+            .into()
+    }
+}
+
+/// Circular progress indicator, small enough to go over a thumbnail.
+pub struct ProgressRing {
+    value: MaybeDyn<f64>,
+    size: &'static str,
+}
+
+impl ProgressRing {
+    /// Radius giving the circle a circumference of 100, so dash lengths are percentages.
+    const RADIUS: &str = "15.915";
+
+    /// Creates a ring filled up to `value`, a fraction between `0.0` and `1.0`.
+    pub fn new(value: impl Into<MaybeDyn<f64>>) -> Self {
+        Self {
+            value: value.into(),
+            size: "28",
+        }
+    }
+
+    pub fn size(mut self, size: &'static str) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl From<ProgressRing> for View {
+    fn from(ring: ProgressRing) -> Self {
+        let value = ring.value;
+        let percent = create_memo(move || (value.get().clamp(0.0, 1.0) * 100.0).round());
+
+        let circle = |class: &'static str| {
+            circle()
+                .class(class)
+                .attr("cx", "18")
+                .attr("cy", "18")
+                .attr("r", ProgressRing::RADIUS)
+                .attr("stroke", "currentColor")
+                .attr("stroke-width", "4")
+        };
+
+        svg()
+            .viewBox("0 0 36 36")
+            .width(ring.size)
+            .height(ring.size)
+            .attr("role", "progressbar")
+            .attr("aria-valuemin", "0")
+            .attr("aria-valuemax", "100")
+            .attr("aria-valuenow", move || percent.get().to_string())
+            .children(circle(tw!(TextColor::Gray300)).attr("fill", "rgb(0 0 0 / 0.5)"))
+            .children(
+                circle(tw!(TextColor::Red500))
+                    .attr("fill", "none")
+                    .attr("stroke-dasharray", move || format!("{} 100", percent.get()))
+                    // Starts from the top instead of the right
+                    .attr("transform", "rotate(-90 18 18)"),
+            )
+            .into()
+    }
+}
@@ -0,0 +1,87 @@
+use rustwind::typography::TextColor;
+use sycamore::{
+    prelude::HtmlAAttributes,
+    web::{
+        events::{click, MouseEvent},
+        tags::{a, br, div, em, p, strong},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::console;
+
+use crate::{
+    rich_text::{self, RichNode, RichTag},
+    tauri, tw,
+};
+
+/// Text from a source that may contain HTML, keeping only basic formatting.
+///
+/// Links open in the default browser of the system, instead of the app's webview.
+pub struct RichText {
+    nodes: Vec<RichNode>,
+    class: &'static str,
+}
+
+impl RichText {
+    pub fn new(html: &str) -> Self {
+        Self {
+            nodes: rich_text::parse(html.trim()),
+            class: "",
+        }
+    }
+
+    pub fn class(mut self, class: &'static str) -> Self {
+        self.class = class;
+        self
+    }
+
+    fn nodes(nodes: Vec<RichNode>) -> Vec<View> {
+        nodes.into_iter().map(Self::node).collect()
+    }
+
+    fn node(node: RichNode) -> View {
+        match node {
+            RichNode::Text(text) => text.into(),
+            RichNode::LineBreak => br().into(),
+            RichNode::Element(tag, children) => {
+                let children = Self::nodes(children);
+                match tag {
+                    RichTag::Bold => strong().children(children).into(),
+                    RichTag::Italic => em().children(children).into(),
+                    RichTag::Paragraph => p().children(children).into(),
+                    RichTag::Link(href) => a()
+                        .href(href.clone())
+                        .attr("target", "_blank")
+                        .attr("rel", "noopener noreferrer")
+                        .class(tw!(TextColor::Red500))
+                        .children(children)
+                        .on(click, move |event: MouseEvent| {
+                            // Outside of the app the link opens in a new tab instead
+                            if !tauri::is_available() {
+                                return;
+                            }
+                            event.prevent_default();
+
+                            let href = href.clone();
+                            spawn_local(async move {
+                                if let Err(err) = tauri::open(&href).await {
+                                    console::warn_2(&"Couldn't open the link".into(), &err);
+                                }
+                            });
+                        })
+                        .into(),
+                }
+            }
+        }
+    }
+}
+
+impl From<RichText> for View {
+    fn from(rich_text: RichText) -> Self {
+        div()
+            .class(rich_text.class)
+            .children(RichText::nodes(rich_text.nodes))
+            .into()
+    }
+}
@@ -0,0 +1,143 @@
+use rustwind::{
+    borders::BorderRadius,
+    flexbox_grid::{AlignItems, FlexDirection, Gap},
+    layout::{AspectRatio, Display},
+    sizing::{Height, Width},
+    spacing::Padding,
+    transitions_animation::Animation,
+};
+use sycamore::web::{tags::div, GlobalProps, HtmlGlobalAttributes, View};
+
+use crate::{hooks::use_theme, tw, utils::ViewBuilder};
+
+/// Pulsing block standing in for content that is still loading.
+pub struct Skeleton {
+    width: Width,
+    height: Option<Height>,
+    aspect_ratio: Option<AspectRatio>,
+    border_radius: BorderRadius,
+}
+
+impl Skeleton {
+    pub fn new() -> Self {
+        Self {
+            width: Width::Full,
+            height: None,
+            aspect_ratio: None,
+            border_radius: BorderRadius::Lg,
+        }
+    }
+
+    pub fn width(mut self, width: Width) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: Height) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn aspect_ratio(mut self, aspect_ratio: AspectRatio) -> Self {
+        self.aspect_ratio = Some(aspect_ratio);
+        self
+    }
+
+    pub fn border_radius(mut self, border_radius: BorderRadius) -> Self {
+        self.border_radius = border_radius;
+        self
+    }
+
+    /// Placeholder shaped like a small episode card.
+    pub fn episode_card() -> View {
+        div()
+            .class(tw!(Display::Flex, AlignItems::Center, Gap::_4, Padding::P1))
+            .children(
+                Skeleton::new()
+                    .width(Width::_1over2)
+                    .aspect_ratio(AspectRatio::Video),
+            )
+            .children(
+                div()
+                    .class(tw!(Width::_1over2))
+                    .children(SkeletonText::new(2)),
+            )
+            .into()
+    }
+
+    /// Placeholder shaped like a series poster card.
+    pub fn series_poster() -> View {
+        div()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
+            .children(Skeleton::new().height(Height::_64))
+            .children(SkeletonText::new(2))
+            .into()
+    }
+}
+
+impl Default for Skeleton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Skeleton> for View {
+    fn from(skeleton: Skeleton) -> Self {
+        let theme = use_theme();
+        let classes = [
+            Some(skeleton.width.as_class()),
+            skeleton.height.map(|height| height.as_class()),
+            skeleton
+                .aspect_ratio
+                .map(|aspect_ratio| aspect_ratio.as_class()),
+            Some(skeleton.border_radius.as_class()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        div()
+            .attr("aria-hidden", "true")
+            .class(move || {
+                format!(
+                    "{classes} {} {}",
+                    tw!(Animation::Pulse),
+                    theme.get().surface().as_class()
+                )
+            })
+            .into()
+    }
+}
+
+/// Lines of text placeholders, the last one shorter like the end of a paragraph.
+pub struct SkeletonText {
+    lines: usize,
+}
+
+impl SkeletonText {
+    pub fn new(lines: usize) -> Self {
+        Self { lines }
+    }
+}
+
+impl From<SkeletonText> for View {
+    fn from(text: SkeletonText) -> Self {
+        let lines = (1..=text.lines)
+            .map(|line| {
+                Skeleton::new()
+                    .height(Height::_4)
+                    .border_radius(BorderRadius::Md)
+                    .when(line == text.lines && text.lines > 1, |this| {
+                        this.width(Width::_2over3)
+                    })
+                    .into()
+            })
+            .collect::<Vec<View>>();
+
+        div()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
+            .children(lines)
+            .into()
+    }
+}
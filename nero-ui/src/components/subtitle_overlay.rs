@@ -0,0 +1,252 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use rustwind::{
+    interactivity::PointerEvents,
+    layout::{Overflow, Position, TopRightBottomLeft},
+};
+use sycamore::{
+    prelude::{create_signal, on_cleanup, NodeRef, Signal},
+    web::{
+        tags::{div, span},
+        GlobalAttributes, GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::HtmlVideoElement;
+
+use crate::{
+    subtitles::{AssEvent, AssScript, AssSegment},
+    tw,
+};
+
+/// Renders an ASS script over the video, keeping its styles and positioning.
+///
+/// Sizes are relative to the overlay height (`cqh` units), so the script scales
+/// with the player like it would with the video resolution.
+///
+/// The video time is polled every frame, but the events are only rendered again
+/// when the active ones, or their sung karaoke syllables, change.
+pub struct SubtitleOverlay {
+    script: Rc<AssScript>,
+    video_ref: NodeRef,
+}
+
+/// An active event, by index, and the number of its karaoke syllables sung.
+type Cue = (usize, usize);
+
+impl SubtitleOverlay {
+    /// Creates an overlay showing the events of `script` active at the current
+    /// time of the video in `video_ref`.
+    pub fn new(script: AssScript, video_ref: NodeRef) -> Self {
+        Self {
+            script: Rc::new(script),
+            video_ref,
+        }
+    }
+
+    /// Start times of the karaoke syllables of every event, relative to its start.
+    fn syllables(script: &AssScript) -> Vec<Vec<f64>> {
+        script
+            .events
+            .iter()
+            .map(|event| {
+                event
+                    .layout(&script.style(&event.style))
+                    .segments
+                    .iter()
+                    .filter_map(|segment| segment.karaoke.map(|(start, _)| start))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn cues(script: &AssScript, syllables: &[Vec<f64>], time: f64) -> Vec<Cue> {
+        script
+            .active_events(time)
+            .map(|(index, event)| {
+                let elapsed = time - event.start;
+                let sung = syllables[index]
+                    .iter()
+                    .take_while(|start| **start <= elapsed)
+                    .count();
+                (index, sung)
+            })
+            .collect()
+    }
+
+    /// Updates `cues` with the ones active in the video on every animation frame,
+    /// until the overlay is disposed.
+    fn watch_cues(script: Rc<AssScript>, video_ref: NodeRef, cues: Signal<Vec<Cue>>) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        let syllables = SubtitleOverlay::syllables(&script);
+        let frame = Rc::new(Cell::new(None::<i32>));
+        // The callback schedules itself, so it's shared with its own closure
+        let callback = Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>));
+
+        *callback.borrow_mut() = Some(Closure::new({
+            let window = window.clone();
+            let frame = frame.clone();
+            let callback = callback.clone();
+            move || {
+                if let Some(video) = video_ref.try_get() {
+                    let video: HtmlVideoElement = video.unchecked_into();
+                    let current = SubtitleOverlay::cues(&script, &syllables, video.current_time());
+                    if cues.with(|cues| *cues != current) {
+                        cues.set(current);
+                    }
+                }
+
+                if let Some(callback) = callback.borrow().as_ref() {
+                    frame.set(
+                        window
+                            .request_animation_frame(callback.as_ref().unchecked_ref())
+                            .ok(),
+                    );
+                }
+            }
+        }));
+
+        if let Some(callback) = callback.borrow().as_ref() {
+            frame.set(
+                window
+                    .request_animation_frame(callback.as_ref().unchecked_ref())
+                    .ok(),
+            );
+        }
+
+        on_cleanup(move || {
+            if let Some(frame) = frame.get() {
+                let _ = window.cancel_animation_frame(frame);
+            }
+            // Breaks the cycle between the callback and itself
+            callback.borrow_mut().take();
+        });
+    }
+
+    fn event(script: &AssScript, event: &AssEvent, sung: usize) -> View {
+        let (res_x, res_y) = script.play_res;
+        let style = script.style(&event.style);
+        let line = event.layout(&style);
+
+        // Columns and rows of the numpad alignment, from the bottom left
+        let column = (line.alignment.clamp(1, 9) - 1) % 3;
+        let row = (line.alignment.clamp(1, 9) - 1) / 3;
+
+        let (x, y) = line.position.unwrap_or_else(|| {
+            let x = match column {
+                0 => style.margin_l,
+                1 => res_x / 2.0,
+                _ => res_x - style.margin_r,
+            };
+            let y = match row {
+                0 => res_y - style.margin_v,
+                1 => res_y / 2.0,
+                _ => style.margin_v,
+            };
+            (x, y)
+        });
+
+        let css = format!(
+            "position: absolute; left: {:.3}%; top: {:.3}%; transform: translate({}%, {}%); \
+             text-align: {}; white-space: pre-wrap;",
+            x / res_x * 100.0,
+            y / res_y * 100.0,
+            [0, -50, -100][column as usize],
+            [-100, -50, 0][row as usize],
+            ["left", "center", "right"][column as usize],
+        );
+
+        let mut syllable = 0;
+        div()
+            .style(css)
+            .children(
+                line.segments
+                    .into_iter()
+                    .map(|segment| {
+                        // Karaoke syllables switch from the secondary to the primary
+                        // color once sung
+                        let sung = match segment.karaoke {
+                            Some(_) => {
+                                syllable += 1;
+                                syllable <= sung
+                            }
+                            None => true,
+                        };
+                        Self::segment(segment, sung, res_y)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .into()
+    }
+
+    fn segment(segment: AssSegment, sung: bool, res_y: f64) -> View {
+        let style = &segment.style;
+        let color = match sung {
+            true => style.primary,
+            false => style.secondary,
+        };
+        let outline = style.outline_width / res_y * 100.0;
+        let decoration = match (style.underline, style.strike_out) {
+            (true, true) => "underline line-through",
+            (true, false) => "underline",
+            (false, true) => "line-through",
+            (false, false) => "none",
+        };
+
+        let css = format!(
+            "font-family: '{}', sans-serif; font-size: {:.3}cqh; color: {}; font-weight: {}; \
+             font-style: {}; text-decoration: {decoration}; text-shadow: {};",
+            style.font_name,
+            style.font_size / res_y * 100.0,
+            color.css(),
+            if style.bold { "bold" } else { "normal" },
+            if style.italic { "italic" } else { "normal" },
+            [(-1, -1), (1, -1), (-1, 1), (1, 1)]
+                .map(|(x, y)| format!(
+                    "{:.3}cqh {:.3}cqh 0 {}",
+                    x as f64 * outline,
+                    y as f64 * outline,
+                    style.outline.css()
+                ))
+                .join(", "),
+        );
+
+        span().style(css).children(segment.text).into()
+    }
+}
+
+impl From<SubtitleOverlay> for View {
+    fn from(overlay: SubtitleOverlay) -> Self {
+        let script = overlay.script;
+        let cues = create_signal(Vec::new());
+
+        SubtitleOverlay::watch_cues(script.clone(), overlay.video_ref, cues);
+
+        let events = move || {
+            cues.with(|cues| {
+                cues.iter()
+                    .map(|(index, sung)| {
+                        SubtitleOverlay::event(&script, &script.events[*index], *sung)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        };
+
+        div()
+            .class(tw!(
+                Position::Absolute,
+                TopRightBottomLeft::Inset0,
+                Overflow::Hidden,
+                PointerEvents::None
+            ))
+            .style("container-type: size;")
+            .children(events)
+            .into()
+    }
+}
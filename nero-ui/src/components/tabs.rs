@@ -0,0 +1,187 @@
+use rustwind::{
+    borders::{BorderColor, BorderWidth},
+    flexbox_grid::{FlexDirection, Gap},
+    layout::Display,
+    spacing::Padding,
+    typography::{FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{create_effect, Signal},
+    web::{
+        events::{click, keydown, KeyboardEvent},
+        tags::{button, div},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Element, HtmlElement, Url};
+
+use crate::tw;
+
+/// Tab list switching between panels, with the index of the active tab in `active`.
+///
+/// The tab list is browsed with the arrow keys, Home and End once focused.
+pub struct Tabs {
+    active: Signal<usize>,
+    tabs: Vec<(&'static str, View)>,
+    query_param: Option<&'static str>,
+}
+
+impl Tabs {
+    pub fn new(active: Signal<usize>) -> Self {
+        Self {
+            active,
+            tabs: Vec::new(),
+            query_param: None,
+        }
+    }
+
+    pub fn tab(mut self, label: &'static str, panel: impl Into<View>) -> Self {
+        self.tabs.push((label, panel.into()));
+        self
+    }
+
+    /// Keeps the active tab in the `name` query parameter of the URL, so it's
+    /// restored when the page is reloaded or shared.
+    pub fn query_param(mut self, name: &'static str) -> Self {
+        self.query_param = Some(name);
+        self
+    }
+
+    /// Value of a tab in the query parameter, e.g. `details` for "Details".
+    fn slug(label: &str) -> String {
+        label.to_lowercase().replace(' ', "-")
+    }
+
+    fn sync_query_param(name: &'static str, labels: Vec<&'static str>, active: Signal<usize>) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        let initial = window
+            .location()
+            .href()
+            .ok()
+            .and_then(|href| Url::new(&href).ok())
+            .and_then(|url| url.search_params().get(name))
+            .and_then(|slug| labels.iter().position(|label| Self::slug(label) == slug));
+        if let Some(index) = initial {
+            active.set(index);
+        }
+
+        create_effect(move || {
+            let Some(label) = labels.get(active.get()) else {
+                return;
+            };
+            let Some(url) = window
+                .location()
+                .href()
+                .ok()
+                .and_then(|href| Url::new(&href).ok())
+            else {
+                return;
+            };
+
+            url.search_params().set(name, &Self::slug(label));
+            // Keeps the state, it identifies the entry for the view state
+            let _ = window.history().and_then(|history| {
+                history.replace_state_with_url(&history.state()?, "", Some(&url.href()))
+            });
+        });
+    }
+}
+
+impl From<Tabs> for View {
+    fn from(tabs: Tabs) -> Self {
+        let active = tabs.active;
+        let count = tabs.tabs.len();
+
+        if let Some(name) = tabs.query_param {
+            let labels = tabs.tabs.iter().map(|(label, _)| *label).collect();
+            Tabs::sync_query_param(name, labels, active);
+        }
+
+        let (labels, panels): (Vec<_>, Vec<_>) = tabs
+            .tabs
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, panel))| {
+                let tab = button()
+                    .attr("role", "tab")
+                    .attr("aria-selected", move || (active.get() == index).to_string())
+                    .attr("tabindex", move || match active.get() == index {
+                        true => "0",
+                        false => "-1",
+                    })
+                    .class(move || match active.get() == index {
+                        true => tw!(
+                            Padding::Px3,
+                            Padding::Py2,
+                            BorderWidth::B2,
+                            BorderColor::Red500,
+                            FontWeight::Semibold
+                        ),
+                        false => tw!(
+                            Padding::Px3,
+                            Padding::Py2,
+                            BorderWidth::B2,
+                            BorderColor::Transparent,
+                            TextColor::Gray500
+                        ),
+                    })
+                    .children(label)
+                    .on(click, move |_| active.set(index));
+
+                let panel = div()
+                    .attr("role", "tabpanel")
+                    .class(move || match active.get() == index {
+                        true => tw!(Display::Block),
+                        false => tw!(Display::Hidden),
+                    })
+                    .children(panel);
+
+                (View::from(tab), View::from(panel))
+            })
+            .unzip();
+
+        let on_keydown = move |event: KeyboardEvent| {
+            if count == 0 {
+                return;
+            }
+            let current = active.get();
+            let next = match event.key().as_str() {
+                "ArrowRight" => (current + 1) % count,
+                "ArrowLeft" => (current + count - 1) % count,
+                "Home" => 0,
+                "End" => count - 1,
+                _ => return,
+            };
+            // The arrows also seek the player, the tab list takes precedence while focused
+            event.prevent_default();
+            event.stop_propagation();
+            active.set(next);
+
+            let list: Element = event.current_target().unwrap_throw().unchecked_into();
+            if let Ok(Some(tab)) = list.query_selector("[aria-selected=\"true\"]") {
+                let _ = tab.unchecked_into::<HtmlElement>().focus();
+            }
+        };
+
+        div()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_4))
+            .children(
+                div()
+                    .attr("role", "tablist")
+                    .class(tw!(
+                        Display::Flex,
+                        Gap::_2,
+                        BorderWidth::B,
+                        BorderColor::Gray200
+                    ))
+                    .on(keydown, on_keydown)
+                    .children(labels),
+            )
+            .children(panels)
+            .into()
+    }
+}
@@ -0,0 +1,104 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    effects::BoxShadow,
+    flexbox_grid::{AlignItems, FlexDirection, Gap},
+    layout::{Display, Position, TopRightBottomLeft, ZIndex},
+    sizing::Width,
+    spacing::Padding,
+    svg::Fill,
+    typography::{FontSize, TextColor},
+};
+use sycamore::web::{
+    tags::{div, p, span},
+    GlobalProps, HtmlGlobalAttributes, View,
+};
+
+use crate::{
+    hooks::{use_toast, Toast, ToastKind, Toaster},
+    tw,
+};
+
+use super::{Button, Icon, IconType};
+
+/// Renders the toasts shown through [`use_toast`], stacked in a corner of the page.
+pub struct ToastStack;
+
+impl ToastStack {
+    fn toast(toaster: Toaster, toast: Toast) -> View {
+        let id = toast.id();
+        let color = match toast.kind {
+            ToastKind::Info => BackgroundColor::Gray800,
+            ToastKind::Success => BackgroundColor::Green600,
+            ToastKind::Error => BackgroundColor::Red500,
+        };
+
+        div()
+            .class(format!(
+                "{} {}",
+                tw!(
+                    Display::Flex,
+                    AlignItems::Center,
+                    Gap::_4,
+                    Padding::Px3,
+                    Padding::Py1_5,
+                    BorderRadius::Lg,
+                    BoxShadow::Lg,
+                    TextColor::White
+                ),
+                color.as_class()
+            ))
+            .children(
+                p().class(tw!(Width::Full, FontSize::Sm))
+                    .children(toast.message),
+            )
+            .children(match toast.action {
+                // The toast text is white, so the label gets a color of its own
+                Some((label, action)) => Button::new(
+                    span().class(tw!(TextColor::Black)).children(label),
+                    move |_| {
+                        action();
+                        toaster.dismiss(id);
+                    },
+                )
+                .color(BackgroundColor::White)
+                .into(),
+                None => View::default(),
+            })
+            .children(Button::icon(
+                Icon::new(IconType::Close)
+                    .width("12")
+                    .height("12")
+                    .fill(Fill::White),
+                move |_| toaster.dismiss(id),
+            ))
+            .into()
+    }
+}
+
+impl From<ToastStack> for View {
+    fn from(_: ToastStack) -> Self {
+        let toaster = use_toast();
+        let toasts = toaster.toasts();
+
+        div()
+            .class(tw!(
+                Position::Fixed,
+                TopRightBottomLeft::Bottom4,
+                TopRightBottomLeft::Right4,
+                ZIndex::_50,
+                Display::Flex,
+                FlexDirection::Col,
+                Gap::_2,
+                Width::_80
+            ))
+            .children(move || {
+                toasts
+                    .get_clone()
+                    .into_iter()
+                    .map(|toast| ToastStack::toast(toaster, toast))
+                    .collect::<Vec<_>>()
+            })
+            .into()
+    }
+}
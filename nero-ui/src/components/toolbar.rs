@@ -1,20 +1,96 @@
-use rustwind::{flexbox_grid::JustifyContent, layout::Display, sizing::Width};
-use sycamore::web::{
-    tags::{nav, p},
-    GlobalProps, HtmlGlobalAttributes, View,
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    flexbox_grid::{AlignItems, Gap, JustifyContent},
+    layout::{Display, Overflow},
+    sizing::{Height, Width},
+    spacing::Padding,
+    typography::{FontSize, TextColor},
 };
+use sycamore::{
+    prelude::create_signal,
+    web::{
+        events::click,
+        tags::{button, div, nav, p, pre, span},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use sycamore_router::navigate;
+
+use crate::{hooks::use_app_state, routes::AppRoutes, tw};
 
-use crate::tw;
+use super::{Button, Icon, IconType, Modal};
 
 pub struct Toolbar;
 
 impl From<Toolbar> for View {
     fn from(_: Toolbar) -> Self {
+        let app = use_app_state();
+        let online = app.online;
+        let errors = app.errors;
+        let errors_open = create_signal(false);
+
         nav()
             .class(tw!(Display::Flex, Width::Full, JustifyContent::Between))
             .children(p().children("Toolbar goes here!"))
             .children(p().children("Options goes here..."))
             .children(p().children("And more options here..."))
+            .children(
+                div()
+                    .class(tw!(Display::Flex, AlignItems::Center, Gap::_2))
+                    .children(move || match online.get() {
+                        true => View::default(),
+                        false => span()
+                            .class(tw!(
+                                Padding::Px2,
+                                Padding::Py0_5,
+                                BorderRadius::Full,
+                                FontSize::Xs,
+                                BackgroundColor::Red500,
+                                TextColor::White
+                            ))
+                            .children("Offline")
+                            .into(),
+                    })
+                    .children(move || match errors.is_empty() {
+                        true => View::default(),
+                        false => button()
+                            .class(move || {
+                                let color = match errors.has_recent() {
+                                    true => BackgroundColor::Amber500,
+                                    false => BackgroundColor::Gray300,
+                                };
+                                format!(
+                                    "{} {}",
+                                    tw!(Width::_3, Height::_3, BorderRadius::Full),
+                                    color.as_class()
+                                )
+                            })
+                            .attr("aria-label", "Recent errors")
+                            .on(click, move |_| errors_open.set(true))
+                            .into(),
+                    })
+                    .children(Button::icon(Icon::new(IconType::Settings), |_| {
+                        navigate(AppRoutes::Settings.path())
+                    })),
+            )
+            .children(
+                Modal::new(
+                    errors_open,
+                    (
+                        pre()
+                            .class(tw!(FontSize::Xs, Overflow::Auto))
+                            .children(move || errors.report()),
+                        div()
+                            .class(tw!(Display::Flex, JustifyContent::End, Gap::_2))
+                            .children(Button::label("Clear", move |_| {
+                                errors.clear();
+                                errors_open.set(false);
+                            })),
+                    ),
+                )
+                .title("Errors"),
+            )
             .into()
     }
 }
@@ -0,0 +1,972 @@
+use std::rc::Rc;
+
+use js_sys::{Function, Reflect};
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    effects::BoxShadow,
+    flexbox_grid::{FlexDirection, FlexWrap, Gap},
+    layout::{AspectRatio, Display, Position, TopRightBottomLeft, ZIndex},
+    sizing::{Height, Width},
+    spacing::Padding,
+    svg::Fill,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{
+        create_effect, create_memo, create_node_ref, create_signal, on_cleanup, on_mount,
+        HtmlInputAttributes, HtmlTrackAttributes, NodeRef, Signal,
+    },
+    web::{
+        events::{
+            change, ended, error, load, loadeddata, loadedmetadata, pause, play, ratechange,
+            timeupdate, Event, MouseEvent,
+        },
+        tags::{div, h3, input, p, section, span, track, video},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue, UnwrapThrowExt};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    console, Document, EventTarget, HtmlElement, HtmlInputElement, HtmlTrackElement,
+    HtmlVideoElement, TextTrackMode, Url,
+};
+
+use crate::{
+    format,
+    hooks::{
+        use_app_state, use_feedback, use_keyboard_shortcuts, use_toast, ErrorRecord, ErrorSource,
+        FeedbackEvent, KeyboardShortcuts, PlaybackStatus,
+    },
+    session::SessionStats,
+    storage::{EpisodeSubtitles, SubtitleFile},
+    stream::{self, source_url, Playback},
+    subtitles::{self, to_vtt, AssScript, SubtitleFormat},
+    tw,
+    types::Video,
+    utils::ViewBuilder,
+};
+
+use super::{
+    AnimatedIcon, Button, ButtonSize, ButtonVariant, CountdownOverlay, Icon, IconState, IconType,
+    PlayerControls, Skeleton, SubtitleOverlay,
+};
+
+/// How the video is currently displayed.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum DisplayMode {
+    #[default]
+    Inline,
+    Fullscreen,
+    PictureInPicture,
+}
+
+pub struct VideoPlayer {
+    videos: Vec<Video>,
+    display_mode: Option<Signal<DisplayMode>>,
+    start_at: f64,
+    subtitles_key: Option<String>,
+    on_pause: Option<Box<dyn FnMut(HtmlVideoElement)>>,
+    on_progress: Option<Box<dyn FnMut(HtmlVideoElement)>>,
+    on_next: Option<Rc<dyn Fn()>>,
+    tracked: bool,
+}
+
+impl VideoPlayer {
+    /// Seconds skipped by the seek shortcuts.
+    const SEEK_STEP: f64 = 5.0;
+    /// Seconds before the next episode starts when auto-play is enabled.
+    const AUTO_PLAY_DELAY: u32 = 10;
+    /// Seconds of playback between two `on_progress` calls.
+    const PROGRESS_INTERVAL: f64 = 10.0;
+
+    pub fn new(videos: Vec<Video>) -> Self {
+        Self {
+            videos,
+            display_mode: None,
+            start_at: 0.0,
+            subtitles_key: None,
+            on_pause: None,
+            on_progress: None,
+            on_next: None,
+            tracked: true,
+        }
+    }
+
+    pub fn on_pause(mut self, on_pause: impl FnMut(HtmlVideoElement) + 'static) -> Self {
+        self.on_pause = Some(Box::new(on_pause));
+        self
+    }
+
+    /// Keeps `mode` updated with how the video is displayed, e.g. to adapt the page
+    /// around the player.
+    pub fn display_mode(mut self, mode: Signal<DisplayMode>) -> Self {
+        self.display_mode = Some(mode);
+        self
+    }
+
+    /// Sets the position in seconds playback starts from, e.g. to resume an episode.
+    pub fn start_at(mut self, position: f64) -> Self {
+        self.start_at = position;
+        self
+    }
+
+    /// Remembers loaded subtitle files under `key`, usually identifying the episode.
+    pub fn subtitles_key(mut self, key: impl Into<String>) -> Self {
+        self.subtitles_key = Some(key.into());
+        self
+    }
+
+    /// Called periodically while playing, to save the playback position.
+    pub fn on_progress(mut self, on_progress: impl FnMut(HtmlVideoElement) + 'static) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    pub fn on_next(mut self, on_next: impl Fn() + 'static) -> Self {
+        self.on_next = Some(Rc::new(on_next));
+        self
+    }
+
+    /// Plays without recording anything, e.g. for trailers: the session stats, the
+    /// playback status of the app and the saved playback rate are left untouched.
+    pub fn untracked(mut self) -> Self {
+        self.tracked = false;
+        self
+    }
+
+    fn toggle_play(video: &HtmlVideoElement) {
+        match video.paused() {
+            true => {
+                let _ = video.play();
+            }
+            false => {
+                let _ = video.pause();
+            }
+        }
+    }
+
+    fn toggle_fullscreen(container: &HtmlElement) {
+        let Some(document) = container.owner_document() else {
+            return;
+        };
+        match document.fullscreen_element() {
+            Some(_) => document.exit_fullscreen(),
+            None => {
+                let _ = container.request_fullscreen();
+            }
+        }
+    }
+
+    fn current_display_mode(document: &Document) -> DisplayMode {
+        // The Picture-in-Picture API isn't part of web-sys
+        let picture_in_picture = Reflect::get(document, &"pictureInPictureElement".into())
+            .is_ok_and(|element| element.is_truthy());
+
+        match (document.fullscreen_element(), picture_in_picture) {
+            (Some(_), _) => DisplayMode::Fullscreen,
+            (None, true) => DisplayMode::PictureInPicture,
+            (None, false) => DisplayMode::Inline,
+        }
+    }
+
+    fn picture_in_picture_enabled() -> bool {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| Reflect::get(&document, &"pictureInPictureEnabled".into()).ok())
+            .is_some_and(|enabled| enabled.is_truthy())
+    }
+
+    fn toggle_picture_in_picture(video: &HtmlVideoElement) {
+        let Some(document) = video.owner_document() else {
+            return;
+        };
+        let active = Self::current_display_mode(&document) == DisplayMode::PictureInPicture;
+        let (target, method): (&JsValue, _) = match active {
+            true => (document.as_ref(), "exitPictureInPicture"),
+            false => (video.as_ref(), "requestPictureInPicture"),
+        };
+
+        if let Ok(method) = Reflect::get(target, &method.into()) {
+            let _ = method.unchecked_into::<Function>().call0(target);
+        }
+    }
+
+    /// Keeps `mode` in sync with the document, since the browser can also leave
+    /// fullscreen or Picture-in-Picture (e.g. with Escape).
+    fn watch_display_mode(video_ref: NodeRef, mode: Signal<DisplayMode>) {
+        on_mount(move || {
+            let video: HtmlVideoElement = video_ref.get().unchecked_into();
+            let Some(document) = video.owner_document() else {
+                return;
+            };
+
+            let listener = Closure::<dyn FnMut()>::new({
+                let document = document.clone();
+                move || mode.set(Self::current_display_mode(&document))
+            });
+            let targets: [(&EventTarget, &str); 3] = [
+                (&document, "fullscreenchange"),
+                (&video, "enterpictureinpicture"),
+                (&video, "leavepictureinpicture"),
+            ];
+            for (target, event) in targets {
+                let _ = target
+                    .add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+            }
+
+            on_cleanup(move || {
+                let targets: [(&EventTarget, &str); 3] = [
+                    (&document, "fullscreenchange"),
+                    (&video, "enterpictureinpicture"),
+                    (&video, "leavepictureinpicture"),
+                ];
+                for (target, event) in targets {
+                    let _ = target.remove_event_listener_with_callback(
+                        event,
+                        listener.as_ref().unchecked_ref(),
+                    );
+                }
+            });
+        });
+    }
+
+    fn shortcuts(
+        video_ref: NodeRef,
+        container_ref: NodeRef,
+        on_next: Option<Rc<dyn Fn()>>,
+    ) -> KeyboardShortcuts {
+        let video = move || video_ref.get().unchecked_into::<HtmlVideoElement>();
+        let seek = move |offset: f64| {
+            let video = video();
+            video.set_current_time((video.current_time() + offset).max(0.0));
+        };
+
+        let shortcuts = KeyboardShortcuts::new()
+            .on(" ", move || Self::toggle_play(&video()))
+            .on("ArrowLeft", move || seek(-Self::SEEK_STEP))
+            .on("ArrowRight", move || seek(Self::SEEK_STEP))
+            .on("m", move || {
+                let video = video();
+                video.set_muted(!video.muted());
+            })
+            .on("f", move || {
+                Self::toggle_fullscreen(&container_ref.get().unchecked_into())
+            });
+
+        match on_next {
+            Some(on_next) => shortcuts.on("n", move || on_next()),
+            None => shortcuts,
+        }
+    }
+
+    /// Picks the video matching the preferred resolution, falling back to the first one.
+    fn initial_video(videos: &[Video], preferred: Option<u16>) -> Option<Video> {
+        videos
+            .iter()
+            .find(|video| Some(video.resolution.0) == preferred)
+            .or(videos.first())
+            .cloned()
+    }
+
+    fn resolution_label(video: &Video) -> String {
+        match video.resolution.0 {
+            0 => "Auto".to_owned(),
+            height => format!("{height}p"),
+        }
+    }
+
+    fn menu_option(
+        label: String,
+        selected: bool,
+        on_click: impl FnMut(MouseEvent) + 'static,
+    ) -> View {
+        Button::new(span().children(label), on_click)
+            .size(ButtonSize::Small)
+            .variant(match selected {
+                true => ButtonVariant::Primary,
+                false => ButtonVariant::Secondary,
+            })
+            .into()
+    }
+
+    fn menu_section(title: &'static str, options: Vec<View>) -> View {
+        section()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
+            .children(
+                h3().class(tw!(FontSize::Sm, FontWeight::Semibold, TextColor::Gray500))
+                    .children(title),
+            )
+            .children(
+                div()
+                    .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_2))
+                    .children(options),
+            )
+            .into()
+    }
+}
+
+impl From<VideoPlayer> for View {
+    fn from(player: VideoPlayer) -> Self {
+        let videos = player.videos;
+        let app = use_app_state();
+        let settings = app.settings;
+        let status = app.playback;
+        let errors = app.errors;
+        let feedback = use_feedback();
+        let tracked = player.tracked;
+        let preferred = settings.with(|settings| settings.player.resolution);
+        let Some(initial_video) = VideoPlayer::initial_video(&videos, preferred) else {
+            return p().children("No videos available for this episode").into();
+        };
+
+        let current = create_signal(initial_video);
+        let menu_open = create_signal(false);
+        let video_ref = create_node_ref();
+        let container_ref = create_node_ref();
+        let playback = create_signal(None::<Playback>);
+        let finished = create_signal(false);
+        let loading = create_signal(true);
+        let auto_play = create_memo(move || settings.with(|settings| settings.player.auto_play));
+        let session_stats =
+            create_memo(move || settings.with(|settings| settings.player.session_stats));
+        let break_reminder =
+            create_memo(move || settings.with(|settings| settings.player.break_reminder));
+        let stats = create_signal(SessionStats::get());
+        let last_time = create_signal(0.0);
+        let last_saved = create_signal(0.0);
+        let resume_at = create_signal(player.start_at);
+        let subtitles_key = player.subtitles_key;
+        let subtitle = create_signal(subtitles_key.as_deref().and_then(EpisodeSubtitles::get));
+        // Index of the source subtitles shown, if they aren't a file loaded by the user
+        let source_track = create_signal(None::<usize>);
+        // URL of the source subtitles being fetched, responses for any other are stale
+        let pending_track = create_signal(None::<String>);
+        let file_ref = create_node_ref();
+        let mut on_pause = player.on_pause;
+        let mut on_progress = player.on_progress;
+        let on_next = player.on_next;
+
+        let display_mode = player
+            .display_mode
+            .unwrap_or_else(|| create_signal(DisplayMode::default()));
+        VideoPlayer::watch_display_mode(video_ref, display_mode);
+        let fullscreen = create_memo(move || display_mode.get() == DisplayMode::Fullscreen);
+
+        let shortcuts = VideoPlayer::shortcuts(video_ref, container_ref, on_next.clone());
+        if let Err(conflict) = use_keyboard_shortcuts(shortcuts) {
+            console::warn_1(&conflict.to_string().into());
+        }
+
+        // The proxy refuses the streams until they are declared
+        let declared = create_signal(false);
+        spawn_local({
+            let videos = videos.clone();
+            async move {
+                stream::declare_streams(&videos).await;
+                if declared.is_alive() {
+                    declared.set(true);
+                }
+            }
+        });
+
+        // Sources are attached manually since HLS and DASH streams can't be set as `src`
+        on_mount(move || {
+            create_effect(move || {
+                if !declared.get() {
+                    return;
+                }
+                let url = current.with(source_url);
+                let element: HtmlVideoElement = video_ref.get().unchecked_into();
+                let rate = settings.with_untracked(|settings| settings.player.playback_rate);
+                loading.set(true);
+
+                // A new source resets the speed to the default one
+                element.set_default_playback_rate(rate);
+                element.set_playback_rate(rate);
+
+                playback.update(|playback| {
+                    if let Some(previous) = playback.take() {
+                        previous.detach();
+                    }
+                    *playback = Some(Playback::attach(&element, &url));
+                });
+            });
+        });
+        on_cleanup(move || {
+            playback.update(|playback| {
+                if let Some(previous) = playback.take() {
+                    previous.detach();
+                }
+            });
+            if tracked {
+                status.set(PlaybackStatus::Idle);
+            }
+        });
+
+        // Switches the source while keeping the playback position, restored once the
+        // new source has loaded since hls.js and dash.js reset the element
+        let select = move |video: Video| {
+            let element: HtmlVideoElement = video_ref.get().unchecked_into();
+            resume_at.set(element.current_time());
+            current.set(video);
+        };
+
+        let toaster = use_toast();
+        let load_subtitles = {
+            let subtitles_key = subtitles_key.clone();
+            move |event: Event| {
+                let input: HtmlInputElement = event.target().unwrap_throw().unchecked_into();
+                let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                    return;
+                };
+                // Allows picking the same file again
+                input.set_value("");
+
+                let Some(format) = SubtitleFormat::from_file_name(&file.name()) else {
+                    toaster.error(format!("{} is not a supported subtitle file", file.name()));
+                    return;
+                };
+                // Files are remembered in the local storage, which only holds a few megabytes
+                if file.size() as u64 > EpisodeSubtitles::MAX_FILE_SIZE as u64 {
+                    toaster.error(format!(
+                        "{} is too large, subtitle files can be up to {}",
+                        file.name(),
+                        format::file_size(EpisodeSubtitles::MAX_FILE_SIZE as u64)
+                    ));
+                    return;
+                }
+
+                let subtitles_key = subtitles_key.clone();
+                spawn_local(async move {
+                    let Ok(content) = JsFuture::from(file.text()).await else {
+                        errors.push(
+                            ErrorRecord::new(ErrorSource::Ui, "Couldn't read subtitle file")
+                                .context(file.name()),
+                        );
+                        toaster.error(format!("Couldn't read {}", file.name()));
+                        return;
+                    };
+                    let file = SubtitleFile {
+                        name: file.name(),
+                        format,
+                        content: content
+                            .as_string()
+                            .unwrap_or_default()
+                            .trim_start_matches('\u{feff}')
+                            .to_owned(),
+                    };
+
+                    // Decoding can make the content longer than the file
+                    if let Some(key) = &subtitles_key {
+                        if !EpisodeSubtitles::set(key, file.clone()) {
+                            toaster.info(format!(
+                                "{} is too large to be remembered for this episode",
+                                file.name
+                            ));
+                        }
+                    }
+                    // The player may have been closed while reading the file
+                    if subtitle.is_alive() {
+                        subtitle.set(Some(file));
+                        source_track.set(None);
+                        pending_track.set(None);
+                    }
+                });
+            }
+        };
+
+        let load_track = move |index: usize| {
+            let Some((track, url)) = current.with(|video| {
+                let track = video.tracks.get(index)?;
+                Some((track.clone(), stream::track_url(video, track)))
+            }) else {
+                return;
+            };
+            pending_track.set(Some(track.url.clone()));
+
+            spawn_local(async move {
+                let content = subtitles::fetch(&url).await;
+                // Another track, a file or no subtitles may have been picked meanwhile
+                if !pending_track.is_alive()
+                    || pending_track.with_untracked(|pending| pending.as_ref() != Some(&track.url))
+                {
+                    return;
+                }
+                pending_track.set(None);
+
+                let Some(content) = content else {
+                    errors.push(
+                        ErrorRecord::new(ErrorSource::Network, "Couldn't load subtitles")
+                            .context(track.url),
+                    );
+                    toaster.error(format!("Couldn't load the {} subtitles", track.language));
+                    return;
+                };
+
+                subtitle.set(Some(SubtitleFile {
+                    name: track.language,
+                    format: track.format,
+                    content,
+                }));
+                source_track.set(Some(index));
+            });
+        };
+
+        // Index and URL of the source subtitles in the preferred language, only
+        // changing when switching to a video that offers a different track
+        let preferred_track = create_memo(move || {
+            let language =
+                settings.with_untracked(|settings| settings.player.subtitle_language.clone());
+            current.with(|video| {
+                video
+                    .tracks
+                    .iter()
+                    .position(|track| Some(&track.language) == language.as_ref())
+                    .map(|index| (index, video.tracks[index].url.clone()))
+            })
+        });
+
+        // Shows the source subtitles in the preferred language, unless the user loaded a file
+        create_effect(move || {
+            // Tracks may be fetched through the proxy as well
+            if !declared.get() {
+                return;
+            }
+            let preferred = preferred_track.get_clone();
+            let user_file =
+                subtitle.with_untracked(Option::is_some) && source_track.get_untracked().is_none();
+
+            match preferred {
+                Some((index, _)) if !user_file => load_track(index),
+                // The previous video's subtitles may not match this one
+                None if !user_file => {
+                    subtitle.set(None);
+                    source_track.set(None);
+                    pending_track.set(None);
+                }
+                _ => {}
+            }
+        });
+
+        let subtitle_track = move || {
+            // ASS scripts are rendered by the overlay instead
+            let Some(file) = subtitle
+                .get_clone()
+                .filter(|file| file.format != SubtitleFormat::Ass)
+            else {
+                return View::default();
+            };
+            let Some(url) = subtitles::object_url(&to_vtt(&file.content, file.format)) else {
+                return View::default();
+            };
+
+            on_cleanup({
+                let url = url.clone();
+                move || {
+                    let _ = Url::revoke_object_url(&url);
+                }
+            });
+
+            track()
+                .kind("subtitles")
+                .label(file.name)
+                .src(url)
+                .default(true)
+                .on(load, |event: Event| {
+                    let element: HtmlTrackElement = event.target().unwrap_throw().unchecked_into();
+                    if let Some(track) = element.track() {
+                        track.set_mode(TextTrackMode::Showing);
+                    }
+                })
+                .into()
+        };
+
+        let subtitle_overlay = move || {
+            let script = subtitle.with(|file| match file {
+                Some(file) if file.format == SubtitleFormat::Ass => {
+                    Some(AssScript::parse(&file.content))
+                }
+                _ => None,
+            });
+
+            match script {
+                Some(script) => SubtitleOverlay::new(script, video_ref).into(),
+                None => View::default(),
+            }
+        };
+
+        let mut servers = Vec::<String>::new();
+        for video in &videos {
+            if !servers.contains(&video.server) {
+                servers.push(video.server.clone());
+            }
+        }
+
+        let menu = move || {
+            if !menu_open.get() {
+                return View::default();
+            }
+
+            let mut subtitle_options = vec![VideoPlayer::menu_option(
+                "Load file…".to_owned(),
+                false,
+                move |_| file_ref.get().unchecked_into::<HtmlElement>().click(),
+            )];
+            let languages = current.with(|video| {
+                video
+                    .tracks
+                    .iter()
+                    .map(|track| track.language.clone())
+                    .collect::<Vec<_>>()
+            });
+            for (index, language) in languages.into_iter().enumerate() {
+                subtitle_options.push(VideoPlayer::menu_option(
+                    language.clone(),
+                    source_track.get() == Some(index),
+                    move |_| {
+                        let language = Some(language.clone());
+                        settings.update(|settings| settings.player.subtitle_language = language);
+                        load_track(index);
+                    },
+                ));
+            }
+            if let Some(name) = subtitle.with(|file| file.as_ref().map(|file| file.name.clone())) {
+                let subtitles_key = subtitles_key.clone();
+
+                if source_track.get().is_none() {
+                    subtitle_options.push(VideoPlayer::menu_option(name, true, |_| {}));
+                }
+                subtitle_options.push(VideoPlayer::menu_option(
+                    "Off".to_owned(),
+                    false,
+                    move |_| {
+                        if let Some(key) = &subtitles_key {
+                            EpisodeSubtitles::remove(key);
+                        }
+                        if source_track.get().is_some() {
+                            settings.update(|settings| settings.player.subtitle_language = None);
+                        }
+                        subtitle.set(None);
+                        source_track.set(None);
+                        pending_track.set(None);
+                    },
+                ));
+            }
+
+            let current_video = current.get_clone();
+
+            let server_options = servers
+                .iter()
+                .map(|server| {
+                    // Stay on the same resolution if the other server offers it
+                    let target = videos
+                        .iter()
+                        .filter(|video| video.server == *server)
+                        .find(|video| video.resolution == current_video.resolution)
+                        .or_else(|| videos.iter().find(|video| video.server == *server))
+                        .cloned()
+                        .unwrap_throw();
+
+                    VideoPlayer::menu_option(
+                        server.clone(),
+                        *server == current_video.server,
+                        move |_| select(target.clone()),
+                    )
+                })
+                .collect();
+
+            let resolution_options = videos
+                .iter()
+                .filter(|video| video.server == current_video.server)
+                .map(|video| {
+                    let target = video.clone();
+
+                    VideoPlayer::menu_option(
+                        VideoPlayer::resolution_label(video),
+                        video.resolution == current_video.resolution,
+                        move |_| {
+                            settings.update(|settings| {
+                                settings.player.resolution = Some(target.resolution.0)
+                            });
+                            select(target.clone());
+                        },
+                    )
+                })
+                .collect();
+
+            let auto_play_options = [("On", true), ("Off", false)]
+                .map(|(label, enabled)| {
+                    VideoPlayer::menu_option(
+                        label.to_owned(),
+                        auto_play.get() == enabled,
+                        move |_| settings.update(|settings| settings.player.auto_play = enabled),
+                    )
+                })
+                .to_vec();
+
+            let session_stats_options = [("On", true), ("Off", false)]
+                .map(|(label, enabled)| {
+                    VideoPlayer::menu_option(
+                        label.to_owned(),
+                        session_stats.get() == enabled,
+                        move |_| {
+                            settings.update(|settings| settings.player.session_stats = enabled)
+                        },
+                    )
+                })
+                .to_vec();
+
+            let break_reminder_options = [None, Some(2), Some(3), Some(5)]
+                .map(|episodes| {
+                    let label = match episodes {
+                        Some(episodes) => format!("{episodes} episodes"),
+                        None => "Off".to_owned(),
+                    };
+
+                    VideoPlayer::menu_option(label, break_reminder.get() == episodes, move |_| {
+                        settings.update(|settings| settings.player.break_reminder = episodes)
+                    })
+                })
+                .to_vec();
+
+            div()
+                .class(format!(
+                    "{} {}",
+                    tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Top12,
+                        TopRightBottomLeft::Right2,
+                        ZIndex::_10,
+                        Display::Flex,
+                        FlexDirection::Col,
+                        Gap::_4,
+                        Width::_56,
+                        Padding::P3,
+                        BorderRadius::Lg,
+                        BoxShadow::Lg
+                    ),
+                    app.theme.get().page()
+                ))
+                .children(VideoPlayer::menu_section("Server", server_options))
+                .children(VideoPlayer::menu_section("Quality", resolution_options))
+                .children(VideoPlayer::menu_section("Subtitles", subtitle_options))
+                .children(VideoPlayer::menu_section("Autoplay", auto_play_options))
+                .children(VideoPlayer::menu_section(
+                    "Session stats",
+                    session_stats_options,
+                ))
+                .children(VideoPlayer::menu_section(
+                    "Break reminder",
+                    break_reminder_options,
+                ))
+                .into()
+        };
+
+        let hud = move || {
+            if !tracked {
+                return View::default();
+            }
+
+            let current_stats = stats.get();
+            let needs_break = break_reminder
+                .get()
+                .is_some_and(|episodes| current_stats.consecutive_episodes >= episodes);
+
+            if !session_stats.get() && !needs_break {
+                return View::default();
+            }
+
+            div()
+                .class(tw!(
+                    Position::Absolute,
+                    TopRightBottomLeft::Top2,
+                    TopRightBottomLeft::Left2,
+                    ZIndex::_10,
+                    Display::Flex,
+                    FlexDirection::Col,
+                    Gap::_1,
+                    Padding::Px3,
+                    Padding::Py1_5,
+                    BorderRadius::Lg,
+                    BackgroundColor::Gray900,
+                    TextColor::White,
+                    FontSize::Sm
+                ))
+                .when(session_stats.get(), |this| {
+                    this.children(p().children(format!(
+                        "{} watched · {} episodes",
+                        format::duration(current_stats.watched),
+                        current_stats.episodes_completed
+                    )))
+                })
+                .when(needs_break, |this| {
+                    this.children(p().children(format!(
+                        "That's {} episodes in a row, maybe take a break?",
+                        current_stats.consecutive_episodes
+                    )))
+                    .children(Button::label("Dismiss", move |_| {
+                        stats.set(SessionStats::update(|stats| stats.consecutive_episodes = 0))
+                    }))
+                })
+                .into()
+        };
+
+        let countdown = move || match (finished.get() && auto_play.get(), on_next.clone()) {
+            (true, Some(on_next)) => CountdownOverlay::new(
+                "Next episode",
+                VideoPlayer::AUTO_PLAY_DELAY,
+                move || on_next(),
+                move || finished.set(false),
+            )
+            .into(),
+            _ => View::default(),
+        };
+
+        div()
+            .r#ref(container_ref)
+            .class(tw!(Position::Relative, Width::Full))
+            .children(
+                video()
+                    .r#ref(video_ref)
+                    .class(tw!(Width::Full, AspectRatio::Video))
+                    .on(loadeddata, move |_| loading.set(false))
+                    .on(error, move |_| {
+                        let video = current.with(|video| {
+                            format!("{} {}", video.server, VideoPlayer::resolution_label(video))
+                        });
+                        errors.push(
+                            ErrorRecord::new(ErrorSource::Network, "Couldn't load the video")
+                                .context(video),
+                        );
+                    })
+                    .on(play, move |_| {
+                        finished.set(false);
+                        if tracked {
+                            status.set(PlaybackStatus::Playing);
+                        }
+                    })
+                    .on(ended, move |_| {
+                        finished.set(true);
+                        if !tracked {
+                            return;
+                        }
+                        feedback.emit(FeedbackEvent::Completed);
+                        stats.set(SessionStats::update(|stats| {
+                            stats.episodes_completed += 1;
+                            stats.consecutive_episodes += 1;
+                        }));
+                    })
+                    .on(timeupdate, move |event: Event| {
+                        let video: HtmlVideoElement =
+                            event.target().unwrap_throw().unchecked_into();
+                        let time = video.current_time();
+                        let elapsed = time - last_time.replace(time);
+
+                        // Seeking also fires this event, only count regular playback
+                        if tracked && !video.paused() && elapsed > 0.0 && elapsed < 2.0 {
+                            stats.set(SessionStats::update(|stats| stats.watched += elapsed));
+                        }
+
+                        if (time - last_saved.get()).abs() >= VideoPlayer::PROGRESS_INTERVAL {
+                            last_saved.set(time);
+                            if let Some(on_progress) = on_progress.as_mut() {
+                                on_progress(video);
+                            }
+                        }
+                    })
+                    .on(loadedmetadata, move |event: Event| {
+                        let position = resume_at.replace(0.0);
+                        if position > 0.0 {
+                            let video: HtmlVideoElement =
+                                event.target().unwrap_throw().unchecked_into();
+                            video.set_current_time(position);
+                        }
+                    })
+                    .on(ratechange, move |event: Event| {
+                        let video: HtmlVideoElement =
+                            event.target().unwrap_throw().unchecked_into();
+                        let rate = video.playback_rate();
+
+                        video.set_default_playback_rate(rate);
+                        if tracked
+                            && settings
+                                .with_untracked(|settings| settings.player.playback_rate != rate)
+                        {
+                            settings.update(|settings| settings.player.playback_rate = rate);
+                        }
+                    })
+                    .on(pause, move |event: Event| {
+                        if tracked {
+                            status.set(PlaybackStatus::Paused);
+                        }
+                        if let Some(on_pause) = on_pause.as_mut() {
+                            on_pause(event.target().unwrap_throw().unchecked_into());
+                        }
+                    })
+                    .children(subtitle_track),
+            )
+            .children(
+                input()
+                    .r#ref(file_ref)
+                    .class(tw!(Display::Hidden))
+                    .r#type("file")
+                    .accept(SubtitleFormat::ACCEPT)
+                    .on(change, load_subtitles),
+            )
+            .children(
+                PlayerControls::new(video_ref).trailing(
+                    div()
+                        .class(tw!(Display::Flex, Gap::_1))
+                        .when(VideoPlayer::picture_in_picture_enabled(), |this| {
+                            this.children(Button::icon(
+                                Icon::new(IconType::PictureInPicture).fill(Fill::White),
+                                move |_| {
+                                    VideoPlayer::toggle_picture_in_picture(
+                                        &video_ref.get().unchecked_into(),
+                                    )
+                                },
+                            ))
+                        })
+                        .children(Button::icon(
+                            AnimatedIcon::new(IconState::fullscreen(*fullscreen)).fill(Fill::White),
+                            move |_| {
+                                VideoPlayer::toggle_fullscreen(
+                                    &container_ref.get().unchecked_into(),
+                                )
+                            },
+                        )),
+                ),
+            )
+            .children(
+                div()
+                    .class(tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Top2,
+                        TopRightBottomLeft::Right2,
+                        ZIndex::_10
+                    ))
+                    .children(Button::icon(
+                        Icon::new(IconType::More).fill(Fill::White),
+                        move |_| menu_open.set(!menu_open.get()),
+                    )),
+            )
+            .children(move || match loading.get() {
+                true => div()
+                    .class(tw!(Position::Absolute, TopRightBottomLeft::Inset0))
+                    .children(
+                        Skeleton::new()
+                            .height(Height::Full)
+                            .border_radius(BorderRadius::None),
+                    )
+                    .into(),
+                false => View::default(),
+            })
+            .children(subtitle_overlay)
+            .children(hud)
+            .children(menu)
+            .children(countdown)
+            .into()
+    }
+}
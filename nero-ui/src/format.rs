@@ -0,0 +1,217 @@
+//! Human-friendly formatting of dates, durations and numbers, following the
+//! language of the browser.
+
+use js_sys::{Array, Date, Intl, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+/// Language the app is displayed in, e.g. `en-US`.
+pub fn locale() -> String {
+    web_sys::window()
+        .and_then(|window| window.navigator().language())
+        .unwrap_or_else(|| "en".to_owned())
+}
+
+fn locales() -> Array {
+    Array::of1(&locale().into())
+}
+
+fn options(entries: &[(&str, &str)]) -> Object {
+    let options = Object::new();
+    for (key, value) in entries {
+        let _ = Reflect::set(&options, &(*key).into(), &(*value).into());
+    }
+    options
+}
+
+/// Formats a `timestamp` in milliseconds relative to now, e.g. `3 days ago`.
+pub fn relative_time(timestamp: f64) -> String {
+    const UNITS: [(f64, &str); 6] = [
+        (60.0, "second"),
+        (60.0, "minute"),
+        (24.0, "hour"),
+        (7.0, "day"),
+        (4.345, "week"),
+        (12.0, "month"),
+    ];
+
+    let mut value = (timestamp - Date::now()) / 1000.0;
+    let mut unit = "year";
+    for (size, name) in UNITS {
+        if value.abs() < size {
+            unit = name;
+            break;
+        }
+        value /= size;
+    }
+
+    Intl::RelativeTimeFormat::new(&locales(), &options(&[("numeric", "auto")]))
+        .format(value.round(), unit)
+        .into()
+}
+
+fn unit(value: f64, unit: &str) -> String {
+    number(
+        value,
+        &[("style", "unit"), ("unit", unit), ("unitDisplay", "short")],
+    )
+}
+
+/// Splits a duration in `seconds` into the values and units it's shown with.
+fn duration_units(seconds: f64) -> Vec<(u32, &'static str)> {
+    let seconds = match seconds.is_finite() {
+        true => seconds.max(0.0).round() as u32,
+        false => 0,
+    };
+    if seconds < 60 {
+        return vec![(seconds, "second")];
+    }
+
+    let minutes = seconds / 60;
+    match minutes / 60 {
+        0 => vec![(minutes, "minute")],
+        hours => vec![(hours, "hour"), (minutes % 60, "minute")],
+    }
+}
+
+/// Formats a duration in `seconds`, e.g. `24 min` or `1 hr 5 min`.
+pub fn duration(seconds: f64) -> String {
+    duration_units(seconds)
+        .into_iter()
+        .map(|(value, name)| unit(value.into(), name))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats a playback position in `seconds` like a clock, e.g. `4:05` or `1:02:09`.
+pub fn clock(seconds: f64) -> String {
+    let seconds = match seconds.is_finite() {
+        true => seconds.max(0.0) as u32,
+        false => 0,
+    };
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    match hours {
+        0 => format!("{minutes}:{seconds:02}"),
+        hours => format!("{hours}:{minutes:02}:{seconds:02}"),
+    }
+}
+
+/// Formats the date of a `timestamp` in milliseconds, e.g. `Oct 16, 2026`.
+pub fn date(timestamp: f64) -> String {
+    Date::new(&timestamp.into())
+        .to_locale_date_string(&locale(), &options(&[("dateStyle", "medium")]).into())
+        .into()
+}
+
+fn number(value: f64, entries: &[(&str, &str)]) -> String {
+    Intl::NumberFormat::new(&locales(), &options(entries))
+        .format()
+        .call1(&JsValue::NULL, &value.into())
+        .ok()
+        .and_then(|formatted| formatted.as_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// Formats a count in short form, e.g. `1.2K` for `1234`.
+pub fn compact(count: usize) -> String {
+    number(count as f64, &[("notation", "compact")])
+}
+
+/// Scales a size in `bytes` to the largest unit it's at least one of.
+fn size_unit(bytes: u64) -> (f64, &'static str) {
+    const UNITS: [&str; 5] = ["byte", "kilobyte", "megabyte", "gigabyte", "terabyte"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+
+    (size, UNITS[unit])
+}
+
+/// Formats a size in `bytes` with the largest fitting unit, e.g. `4.2 MB`.
+pub fn file_size(bytes: u64) -> String {
+    let (size, unit) = size_unit(bytes);
+
+    number(
+        size,
+        &[
+            ("style", "unit"),
+            ("unit", unit),
+            ("unitDisplay", "short"),
+            ("maximumFractionDigits", "1"),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration_units_below_a_minute() {
+        assert_eq!(duration_units(0.0), [(0, "second")]);
+        assert_eq!(duration_units(-5.0), [(0, "second")]);
+        assert_eq!(duration_units(42.4), [(42, "second")]);
+        assert_eq!(duration_units(f64::NAN), [(0, "second")]);
+    }
+
+    #[test]
+    fn duration_units_at_unit_boundaries() {
+        // Rounded up to a whole minute
+        assert_eq!(duration_units(59.6), [(1, "minute")]);
+        assert_eq!(duration_units(60.0), [(1, "minute")]);
+        assert_eq!(duration_units(3599.0), [(59, "minute")]);
+        assert_eq!(duration_units(3600.0), [(1, "hour"), (0, "minute")]);
+    }
+
+    #[test]
+    fn duration_units_above_an_hour() {
+        assert_eq!(duration_units(3900.0), [(1, "hour"), (5, "minute")]);
+        assert_eq!(duration_units(90_000.0), [(25, "hour"), (0, "minute")]);
+    }
+
+    #[test]
+    fn clock_below_a_minute() {
+        assert_eq!(clock(0.0), "0:00");
+        assert_eq!(clock(-3.0), "0:00");
+        assert_eq!(clock(5.9), "0:05");
+        assert_eq!(clock(f64::INFINITY), "0:00");
+    }
+
+    #[test]
+    fn clock_at_unit_boundaries() {
+        assert_eq!(clock(59.0), "0:59");
+        assert_eq!(clock(60.0), "1:00");
+        assert_eq!(clock(3599.0), "59:59");
+        assert_eq!(clock(3600.0), "1:00:00");
+    }
+
+    #[test]
+    fn clock_above_an_hour() {
+        assert_eq!(clock(3729.0), "1:02:09");
+        assert_eq!(clock(36_000.0), "10:00:00");
+    }
+
+    #[test]
+    fn size_unit_below_a_kilobyte() {
+        assert_eq!(size_unit(0), (0.0, "byte"));
+        assert_eq!(size_unit(999), (999.0, "byte"));
+    }
+
+    #[test]
+    fn size_unit_at_unit_boundaries() {
+        assert_eq!(size_unit(1000), (1.0, "kilobyte"));
+        assert_eq!(size_unit(999_999), (999.999, "kilobyte"));
+        assert_eq!(size_unit(1_000_000), (1.0, "megabyte"));
+        assert_eq!(size_unit(2_500_000_000), (2.5, "gigabyte"));
+    }
+
+    #[test]
+    fn size_unit_stops_at_terabytes() {
+        assert_eq!(size_unit(1_000_000_000_000), (1.0, "terabyte"));
+        assert_eq!(size_unit(5_000_000_000_000_000), (5000.0, "terabyte"));
+    }
+}
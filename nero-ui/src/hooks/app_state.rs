@@ -0,0 +1,87 @@
+use sycamore::prelude::{
+    create_signal, on_cleanup, provide_context, use_context, ReadSignal, Signal,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+use crate::storage::AppSettings;
+
+use super::{memory::watch_memory, use_settings, use_theme, ColorScheme, ErrorLog};
+
+/// What the video player is currently doing.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum PlaybackStatus {
+    /// No episode is open.
+    #[default]
+    Idle,
+    Playing,
+    Paused,
+}
+
+/// Episode open in the video player.
+#[derive(Clone, PartialEq)]
+pub struct NowPlaying {
+    pub series_id: String,
+    pub episode_id: String,
+    /// Watched fraction of the episode, between `0.0` and `1.0`.
+    pub progress: f64,
+}
+
+/// State shared by the whole app, provided by the base layout.
+#[derive(Clone, Copy)]
+pub struct AppState {
+    pub settings: Signal<AppSettings>,
+    pub theme: ReadSignal<ColorScheme>,
+    /// Whether the browser has network access.
+    pub online: ReadSignal<bool>,
+    pub playback: Signal<PlaybackStatus>,
+    /// Updated periodically while an episode plays, e.g. to show its progress live.
+    pub now_playing: Signal<Option<NowPlaying>>,
+    pub errors: ErrorLog,
+    // TODO: let users pick the extension once they can be loaded
+    /// Extension series are searched and loaded from.
+    pub extension_id: ReadSignal<String>,
+}
+
+impl AppState {
+    const SAMPLE_EXTENSION_ID: &str = "sample";
+}
+
+fn watch_online() -> ReadSignal<bool> {
+    let Some(window) = web_sys::window() else {
+        return *create_signal(true);
+    };
+    let online = create_signal(window.navigator().on_line());
+
+    for (event, value) in [("online", true), ("offline", false)] {
+        let listener = Closure::<dyn FnMut()>::new(move || online.set(value));
+        let _ = window.add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+
+        let window = window.clone();
+        on_cleanup(move || {
+            let _ = window
+                .remove_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+        });
+    }
+
+    *online
+}
+
+/// Makes the [`AppState`] available to [`use_app_state`] in the current scope.
+///
+/// Must be called after [`super::provide_theme`].
+pub fn provide_app_state() {
+    watch_memory();
+    provide_context(AppState {
+        settings: use_settings(),
+        theme: use_theme(),
+        online: watch_online(),
+        playback: create_signal(PlaybackStatus::default()),
+        now_playing: create_signal(None),
+        errors: ErrorLog::new(),
+        extension_id: *create_signal(AppState::SAMPLE_EXTENSION_ID.to_owned()),
+    });
+}
+
+pub fn use_app_state() -> AppState {
+    use_context()
+}
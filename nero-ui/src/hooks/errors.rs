@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+
+use sycamore::prelude::{create_signal, on_cleanup, Signal};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::ErrorEvent;
+
+use crate::storage;
+
+/// Part of the app an error comes from.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ErrorSource {
+    Ui,
+    Extension,
+    Network,
+    /// Saved data, e.g. a store that had to be recovered.
+    Storage,
+}
+
+impl ErrorSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorSource::Ui => "UI",
+            ErrorSource::Extension => "Extension",
+            ErrorSource::Network => "Network",
+            ErrorSource::Storage => "Storage",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ErrorRecord {
+    pub source: ErrorSource,
+    pub message: String,
+    /// What the app was doing, e.g. the video or file involved.
+    pub context: Option<String>,
+    /// Milliseconds since the Unix epoch when the error happened.
+    pub timestamp: f64,
+}
+
+impl ErrorRecord {
+    pub fn new(source: ErrorSource, message: impl Into<String>) -> Self {
+        Self {
+            source,
+            message: message.into(),
+            context: None,
+            timestamp: js_sys::Date::now(),
+        }
+    }
+
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+}
+
+/// Last errors of the session, kept in memory for diagnostics.
+#[derive(Clone, Copy)]
+pub struct ErrorLog {
+    records: Signal<VecDeque<ErrorRecord>>,
+    /// Errors recorded less than [`ErrorLog::RECENT_DURATION`] ago.
+    recent: Signal<usize>,
+    /// Incremented when the log is cleared, so the expiry of errors recorded
+    /// before doesn't decrement `recent` again.
+    generation: Signal<u32>,
+}
+
+impl ErrorLog {
+    /// Older errors are dropped when more are recorded.
+    const CAPACITY: usize = 50;
+    /// Milliseconds an error counts as recent.
+    const RECENT_DURATION: i32 = 5 * 60 * 1000;
+
+    pub(super) fn new() -> Self {
+        let log = Self {
+            records: create_signal(VecDeque::new()),
+            recent: create_signal(0),
+            generation: create_signal(0),
+        };
+        log.watch_uncaught();
+        storage::on_issue(move |issue| {
+            log.push(ErrorRecord::new(ErrorSource::Storage, issue.message()))
+        });
+        log
+    }
+
+    /// Records uncaught script errors as UI errors.
+    fn watch_uncaught(self) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        let listener = Closure::<dyn FnMut(ErrorEvent)>::new(move |event: ErrorEvent| {
+            let location = format!("{}:{}", event.filename(), event.lineno());
+            self.push(ErrorRecord::new(ErrorSource::Ui, event.message()).context(location));
+        });
+        let _ = window.add_event_listener_with_callback("error", listener.as_ref().unchecked_ref());
+
+        on_cleanup(move || {
+            let _ = window
+                .remove_event_listener_with_callback("error", listener.as_ref().unchecked_ref());
+        });
+    }
+
+    pub fn push(self, record: ErrorRecord) {
+        self.records.update(|records| {
+            records.push_front(record);
+            records.truncate(Self::CAPACITY);
+        });
+        // Updated untracked, since errors are often pushed from effects
+        self.recent.update(|recent| *recent += 1);
+
+        if let Some(window) = web_sys::window() {
+            let generation = self.generation.get_untracked();
+            let expire = Closure::once_into_js(move || {
+                if self.recent.is_alive() && self.generation.get_untracked() == generation {
+                    self.recent
+                        .update(|recent| *recent = recent.saturating_sub(1));
+                }
+            });
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                expire.unchecked_ref(),
+                Self::RECENT_DURATION,
+            );
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.records.with(VecDeque::is_empty)
+    }
+
+    /// Whether any error was recorded in the last few minutes.
+    pub fn has_recent(self) -> bool {
+        self.recent.get() > 0
+    }
+
+    pub fn clear(self) {
+        self.records.set(VecDeque::new());
+        self.recent.set(0);
+        self.generation
+            .set(self.generation.get_untracked().wrapping_add(1));
+    }
+
+    /// Plain text listing of the errors, one per line, for diagnostics bundles.
+    pub fn report(self) -> String {
+        self.records.with(|records| {
+            records
+                .iter()
+                .map(|record| {
+                    let time = js_sys::Date::new(&record.timestamp.into()).to_iso_string();
+                    let mut line = format!("{time} [{}] {}", record.source.label(), record.message);
+                    if let Some(context) = &record.context {
+                        line.push_str(&format!(" ({context})"));
+                    }
+                    line
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+}
@@ -0,0 +1,141 @@
+use sycamore::prelude::{create_signal, provide_context, use_context, ReadSignal, Signal};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::AudioContext;
+
+use crate::storage::{AppSettings, FeedbackMode};
+
+use super::use_settings;
+
+/// Something the user may want to notice without looking at it.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FeedbackEvent {
+    Notification,
+    /// An episode finished playing.
+    Completed,
+    Error,
+}
+
+impl FeedbackEvent {
+    /// Frequencies of the notes of the chime, in hertz.
+    fn notes(self) -> &'static [f32] {
+        match self {
+            FeedbackEvent::Notification => &[880.0],
+            FeedbackEvent::Completed => &[660.0, 880.0],
+            FeedbackEvent::Error => &[330.0, 220.0],
+        }
+    }
+
+    /// Caption describing the event, shown with the visual flash.
+    pub fn caption(self) -> &'static str {
+        match self {
+            FeedbackEvent::Notification => "Notification",
+            FeedbackEvent::Completed => "Episode finished",
+            FeedbackEvent::Error => "Error",
+        }
+    }
+}
+
+/// Routes feedback events to a chime, a visual flash or nothing, following
+/// the feedback setting.
+///
+/// Chimes are always captioned by the flash, so no event relies on sound alone.
+#[derive(Clone, Copy)]
+pub struct Feedback {
+    settings: Signal<AppSettings>,
+    flash: Signal<Option<FeedbackEvent>>,
+    /// Incremented on every flash, so the timer of an earlier one doesn't hide it.
+    sequence: Signal<u32>,
+}
+
+impl Feedback {
+    /// Milliseconds the flash stays on screen.
+    const FLASH_DURATION: i32 = 1200;
+    /// Seconds each note of a chime lasts.
+    const NOTE_DURATION: f64 = 0.12;
+    const VOLUME: f32 = 0.1;
+
+    pub fn emit(self, event: FeedbackEvent) {
+        match self.settings.with(|settings| settings.feedback) {
+            FeedbackMode::Sound => {
+                Self::chime(event);
+                self.flash(event);
+            }
+            FeedbackMode::Visual => self.flash(event),
+            FeedbackMode::Off => {}
+        }
+    }
+
+    /// Event being flashed, if any.
+    pub fn flashing(self) -> ReadSignal<Option<FeedbackEvent>> {
+        *self.flash
+    }
+
+    fn flash(self, event: FeedbackEvent) {
+        self.flash.set(Some(event));
+        let sequence = self.sequence.get_untracked().wrapping_add(1);
+        self.sequence.set(sequence);
+
+        if let Some(window) = web_sys::window() {
+            let hide = Closure::once_into_js(move || {
+                // Keeps a newer flash, even of the same event, on screen for its whole duration
+                if self.flash.is_alive() && self.sequence.get_untracked() == sequence {
+                    self.flash.set(None);
+                }
+            });
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                hide.unchecked_ref(),
+                Self::FLASH_DURATION,
+            );
+        }
+    }
+
+    /// Plays the notes of `event`, synthesized so no sound files are needed.
+    fn chime(event: FeedbackEvent) {
+        let Ok(context) = AudioContext::new() else {
+            return;
+        };
+        let Ok(gain) = context.create_gain() else {
+            return;
+        };
+        gain.gain().set_value(Self::VOLUME);
+        let _ = gain.connect_with_audio_node(&context.destination());
+
+        let start = context.current_time();
+        let notes = event.notes();
+        for (index, frequency) in notes.iter().enumerate() {
+            let Ok(oscillator) = context.create_oscillator() else {
+                return;
+            };
+            oscillator.frequency().set_value(*frequency);
+            let _ = oscillator.connect_with_audio_node(&gain);
+
+            let begin = start + index as f64 * Self::NOTE_DURATION;
+            let _ = oscillator.start_with_when(begin);
+            let _ = oscillator.stop_with_when(begin + Self::NOTE_DURATION);
+
+            // Browsers limit how many audio contexts can be open at once
+            if index == notes.len() - 1 {
+                let context = context.clone();
+                let close = Closure::once_into_js(move || {
+                    let _ = context.close();
+                });
+                oscillator.set_onended(Some(close.unchecked_ref()));
+            }
+        }
+    }
+}
+
+/// Makes the [`Feedback`] service available to [`use_feedback`] in the current scope.
+///
+/// Must be called after [`super::provide_settings`].
+pub fn provide_feedback() {
+    provide_context(Feedback {
+        settings: use_settings(),
+        flash: create_signal(None),
+        sequence: create_signal(0),
+    });
+}
+
+pub fn use_feedback() -> Feedback {
+    use_context()
+}
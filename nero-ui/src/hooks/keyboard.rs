@@ -0,0 +1,143 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+};
+
+use sycamore::prelude::on_cleanup;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{HtmlElement, KeyboardEvent};
+
+type Handler = Rc<RefCell<dyn FnMut()>>;
+
+thread_local! {
+    static REGISTRY: RefCell<HashMap<Shortcut, Handler>> = RefCell::new(HashMap::new());
+    static LISTENING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A key as reported by `KeyboardEvent.key` (e.g. `" "`, `"ArrowLeft"`, `"f"`).
+/// Single characters are matched case-insensitively.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut(String);
+
+impl Shortcut {
+    pub fn new(key: &str) -> Self {
+        match key.chars().count() {
+            1 => Self(key.to_lowercase()),
+            _ => Self(key.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_str() {
+            " " => write!(f, "Space"),
+            key => write!(f, "{key}"),
+        }
+    }
+}
+
+/// Returned when shortcuts are already registered by another component.
+#[derive(Debug)]
+pub struct ShortcutConflict(pub Vec<Shortcut>);
+
+impl fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys = self.0.iter().map(Shortcut::to_string).collect::<Vec<_>>();
+        write!(f, "shortcuts already in use: {}", keys.join(", "))
+    }
+}
+
+#[derive(Default)]
+pub struct KeyboardShortcuts {
+    handlers: Vec<(Shortcut, Handler)>,
+}
+
+impl KeyboardShortcuts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on(mut self, key: &str, handler: impl FnMut() + 'static) -> Self {
+        self.handlers
+            .push((Shortcut::new(key), Rc::new(RefCell::new(handler))));
+        self
+    }
+}
+
+/// Registers the shortcuts until the current reactive scope is disposed.
+///
+/// Shortcuts already owned by another component are left untouched and
+/// reported in the returned error, while the rest are still registered.
+pub fn use_keyboard_shortcuts(shortcuts: KeyboardShortcuts) -> Result<(), ShortcutConflict> {
+    listen();
+
+    let mut registered = Vec::new();
+    let mut conflicts = Vec::new();
+
+    REGISTRY.with_borrow_mut(|registry| {
+        for (shortcut, handler) in shortcuts.handlers {
+            match registry.contains_key(&shortcut) {
+                true => conflicts.push(shortcut),
+                false => {
+                    registry.insert(shortcut.clone(), handler);
+                    registered.push(shortcut);
+                }
+            }
+        }
+    });
+
+    on_cleanup(move || {
+        REGISTRY.with_borrow_mut(|registry| {
+            for shortcut in &registered {
+                registry.remove(shortcut);
+            }
+        })
+    });
+
+    match conflicts.is_empty() {
+        true => Ok(()),
+        false => Err(ShortcutConflict(conflicts)),
+    }
+}
+
+/// Installs the window listener dispatching to the registry, once.
+fn listen() {
+    if LISTENING.replace(true) {
+        return;
+    }
+
+    let listener = Closure::<dyn FnMut(KeyboardEvent)>::new(|event: KeyboardEvent| {
+        if event.ctrl_key() || event.meta_key() || event.alt_key() || is_editable(&event) {
+            return;
+        }
+
+        let shortcut = Shortcut::new(&event.key());
+        // Cloned out so handlers can (un)register shortcuts themselves
+        let handler = REGISTRY.with_borrow(|registry| registry.get(&shortcut).cloned());
+
+        if let Some(handler) = handler {
+            event.prevent_default();
+            (&mut *handler.borrow_mut())();
+        }
+    });
+
+    if let Some(window) = web_sys::window() {
+        let _ =
+            window.add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+    }
+    listener.forget();
+}
+
+/// Whether the event comes from a text field, where keys must keep their usual behavior.
+fn is_editable(event: &KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<HtmlElement>().ok())
+        .is_some_and(|element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || element.is_content_editable()
+        })
+}
@@ -0,0 +1,59 @@
+use sycamore::prelude::{create_signal, on_cleanup, ReadSignal};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::Window;
+
+/// Whether the CSS media `query` matches, updated when it changes.
+///
+/// The listener is removed when the current scope is disposed.
+pub fn use_media_query(query: &str) -> ReadSignal<bool> {
+    let Some(list) = web_sys::window().and_then(|window| window.match_media(query).ok().flatten())
+    else {
+        return *create_signal(false);
+    };
+    let matches = create_signal(list.matches());
+
+    let listener = Closure::<dyn FnMut()>::new({
+        let list = list.clone();
+        move || matches.set(list.matches())
+    });
+    let _ = list.add_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+
+    on_cleanup(move || {
+        let _ =
+            list.remove_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+    });
+
+    *matches
+}
+
+fn inner_size(window: &Window) -> (f64, f64) {
+    let width = window.inner_width().ok().and_then(|width| width.as_f64());
+    let height = window
+        .inner_height()
+        .ok()
+        .and_then(|height| height.as_f64());
+    (width.unwrap_or_default(), height.unwrap_or_default())
+}
+
+/// Width and height of the window viewport in CSS pixels, updated on resize.
+///
+/// Prefer [`use_media_query`] for breakpoints, it only updates when crossing them.
+pub fn use_window_size() -> ReadSignal<(f64, f64)> {
+    let Some(window) = web_sys::window() else {
+        return *create_signal((0.0, 0.0));
+    };
+    let size = create_signal(inner_size(&window));
+
+    let listener = Closure::<dyn FnMut()>::new({
+        let window = window.clone();
+        move || size.set(inner_size(&window))
+    });
+    let _ = window.add_event_listener_with_callback("resize", listener.as_ref().unchecked_ref());
+
+    on_cleanup(move || {
+        let _ =
+            window.remove_event_listener_with_callback("resize", listener.as_ref().unchecked_ref());
+    });
+
+    *size
+}
@@ -0,0 +1,83 @@
+use js_sys::Reflect;
+use sycamore::prelude::{create_effect, create_memo, on_cleanup};
+use wasm_bindgen::{closure::Closure, JsCast};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::image_cache;
+
+use super::{shrink_view_states, use_settings};
+
+/// Bytes of JavaScript heap above which the in-memory caches are shrunk.
+const HEAP_THRESHOLD: f64 = 512.0 * 1024.0 * 1024.0;
+/// Milliseconds between two checks of the heap size.
+const CHECK_INTERVAL: i32 = 30_000;
+
+/// Used JavaScript heap in bytes, only reported by Chromium-based web views.
+fn used_heap() -> Option<f64> {
+    let performance = web_sys::window()?.performance()?;
+    let memory = Reflect::get(&performance, &"memory".into()).ok()?;
+    Reflect::get(&memory, &"usedJSHeapSize".into())
+        .ok()?
+        .as_f64()
+}
+
+/// Evicts the cached images beyond the size picked in the settings as soon as it
+/// is lowered, rather than on the next image added.
+fn watch_image_cache_size() {
+    let settings = use_settings();
+    let max_entries =
+        create_memo(move || settings.with(|settings| settings.image_cache.max_entries()));
+    let mut first = true;
+
+    create_effect(move || {
+        let max_entries = max_entries.get();
+        // The cache was already bounded by the size it was filled with
+        if std::mem::take(&mut first) {
+            return;
+        }
+        spawn_local(async move {
+            let _ = image_cache::evict(max_entries).await;
+        });
+    });
+}
+
+/// Shrinks the in-memory caches whenever the heap grows past [`HEAP_THRESHOLD`],
+/// so long sessions don't slow the web view down.
+pub(super) fn watch_memory() {
+    watch_image_cache_size();
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    // Nothing to watch on web views that don't report it
+    if used_heap().is_none() {
+        return;
+    }
+
+    let settings = use_settings();
+    let check = Closure::<dyn FnMut()>::new(move || {
+        if used_heap().is_some_and(|used| used > HEAP_THRESHOLD) {
+            shrink_view_states();
+            image_cache::release_object_urls();
+            // Halves the cache, the images dropped are fetched again when shown
+            let max_entries =
+                settings.with_untracked(|settings| settings.image_cache.max_entries()) / 2;
+            spawn_local(async move {
+                let _ = image_cache::evict(max_entries).await;
+            });
+        }
+    });
+    let interval = window
+        .set_interval_with_callback_and_timeout_and_arguments_0(
+            check.as_ref().unchecked_ref(),
+            CHECK_INTERVAL,
+        )
+        .ok();
+
+    on_cleanup(move || {
+        if let Some(interval) = interval {
+            window.clear_interval_with_handle(interval);
+        }
+        drop(check);
+    });
+}
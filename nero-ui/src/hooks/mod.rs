@@ -0,0 +1,22 @@
+mod app_state;
+mod errors;
+mod feedback;
+mod keyboard;
+mod media;
+mod memory;
+mod persisted;
+mod settings;
+mod theme;
+mod toast;
+mod view_state;
+
+pub use app_state::*;
+pub use errors::*;
+pub use feedback::*;
+pub use keyboard::*;
+pub use media::*;
+pub use persisted::*;
+pub use settings::*;
+pub use theme::*;
+pub use toast::*;
+pub use view_state::*;
@@ -0,0 +1,64 @@
+use std::{cell::Cell, rc::Rc};
+
+use serde::{de::DeserializeOwned, Serialize};
+use sycamore::prelude::{create_effect, create_signal, on_cleanup, Signal};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+use crate::storage::{load, save};
+
+/// Milliseconds without changes before a persisted signal is written.
+const WRITE_DELAY: i32 = 500;
+
+/// Signal initialized from the value stored under `key`, or `default`, and
+/// saved back whenever it changes.
+///
+/// Writes are debounced so that quickly toggled UI state (e.g. sort order)
+/// only hits the storage once, and a pending write is flushed when the
+/// current scope is disposed.
+pub fn persisted_signal<T>(key: &'static str, default: T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let signal = create_signal(load(key).unwrap_or(default));
+    let pending = Rc::new(Cell::new(None::<i32>));
+    let mut loaded = true;
+
+    create_effect({
+        let pending = pending.clone();
+        move || {
+            signal.track();
+            // The first run only subscribes, the value was just loaded
+            if std::mem::take(&mut loaded) {
+                return;
+            }
+            let Some(window) = web_sys::window() else {
+                return;
+            };
+
+            if let Some(handle) = pending.take() {
+                window.clear_timeout_with_handle(handle);
+            }
+            let write = Closure::once_into_js({
+                let pending = pending.clone();
+                move || {
+                    pending.set(None);
+                    signal.with(|value| save(key, value));
+                }
+            });
+            let handle = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                write.unchecked_ref(),
+                WRITE_DELAY,
+            );
+            pending.set(handle.ok());
+        }
+    });
+
+    on_cleanup(move || {
+        if let (Some(handle), Some(window)) = (pending.take(), web_sys::window()) {
+            window.clear_timeout_with_handle(handle);
+            signal.with(|value| save(key, value));
+        }
+    });
+
+    signal
+}
@@ -0,0 +1,27 @@
+use sycamore::prelude::{create_effect, create_signal, provide_context, use_context, Signal};
+
+use crate::storage::AppSettings;
+
+/// Makes the stored settings available to [`use_settings`] in the current
+/// scope, saving them whenever they change.
+pub fn provide_settings() {
+    let settings = create_signal(AppSettings::load());
+    let mut loaded = true;
+    create_effect(move || {
+        settings.track();
+        // The first run only subscribes: saving what was just loaded would replace
+        // stored settings this version couldn't read
+        if !std::mem::take(&mut loaded) {
+            settings.with(AppSettings::save);
+        }
+    });
+
+    provide_context(settings);
+}
+
+/// Returns the app settings, shared by every component.
+///
+/// Updating the signal persists the new settings.
+pub fn use_settings() -> Signal<AppSettings> {
+    use_context()
+}
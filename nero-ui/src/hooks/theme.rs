@@ -0,0 +1,109 @@
+use rustwind::{backgrounds::BackgroundColor, hover, svg::Fill, typography::TextColor};
+use sycamore::prelude::{
+    create_memo, create_signal, on_cleanup, provide_context, use_context, ReadSignal,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::MediaQueryList;
+
+use crate::{storage::Theme, tw};
+
+use super::use_settings;
+
+const DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+
+/// Theme actually applied, once [`Theme::System`] is resolved.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    /// Classes of the page background and the default text color.
+    pub fn page(self) -> &'static str {
+        match self {
+            ColorScheme::Light => tw!(BackgroundColor::White, TextColor::Black),
+            ColorScheme::Dark => tw!(BackgroundColor::Gray900, TextColor::Gray100),
+        }
+    }
+
+    /// Background of highlighted items, e.g. the focused option of a menu.
+    pub fn surface(self) -> BackgroundColor {
+        match self {
+            ColorScheme::Light => BackgroundColor::Gray100,
+            ColorScheme::Dark => BackgroundColor::Gray800,
+        }
+    }
+
+    /// Classes of form controls (e.g. inputs and chips), a surface with a text
+    /// color readable on it.
+    pub fn control(self) -> &'static str {
+        match self {
+            ColorScheme::Light => tw!(BackgroundColor::Gray100, TextColor::Black),
+            ColorScheme::Dark => tw!(BackgroundColor::Gray800, TextColor::Gray100),
+        }
+    }
+
+    /// Class highlighting interactive surfaces (e.g. cards) when hovered.
+    pub fn hover(self) -> &'static str {
+        match self {
+            ColorScheme::Light => tw!(hover!(BackgroundColor::Gray100)),
+            ColorScheme::Dark => tw!(hover!(BackgroundColor::Gray800)),
+        }
+    }
+
+    /// Default fill of icons, contrasting with the page background.
+    pub fn icon_fill(self) -> Fill {
+        match self {
+            ColorScheme::Light => Fill::Black,
+            ColorScheme::Dark => Fill::White,
+        }
+    }
+}
+
+fn dark_query() -> Option<MediaQueryList> {
+    web_sys::window()?.match_media(DARK_QUERY).ok().flatten()
+}
+
+/// Makes the color scheme available to [`use_theme`] in the current scope,
+/// following the operating system when the theme setting is [`Theme::System`].
+///
+/// Must be called after [`super::provide_settings`].
+pub fn provide_theme() {
+    let settings = use_settings();
+    let query = dark_query();
+    let system_dark = create_signal(query.as_ref().is_some_and(MediaQueryList::matches));
+
+    if let Some(query) = query {
+        let listener = Closure::<dyn FnMut()>::new({
+            let query = query.clone();
+            move || system_dark.set(query.matches())
+        });
+        let _ = query.add_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+
+        on_cleanup(move || {
+            let _ = query
+                .remove_event_listener_with_callback("change", listener.as_ref().unchecked_ref());
+        });
+    }
+
+    let scheme = create_memo(move || {
+        let dark = match settings.with(|settings| settings.theme) {
+            Theme::System => system_dark.get(),
+            Theme::Light => false,
+            Theme::Dark => true,
+        };
+
+        match dark {
+            true => ColorScheme::Dark,
+            false => ColorScheme::Light,
+        }
+    });
+
+    provide_context(scheme);
+}
+
+/// Returns the color scheme components should be rendered with.
+pub fn use_theme() -> ReadSignal<ColorScheme> {
+    use_context()
+}
@@ -0,0 +1,136 @@
+use std::rc::Rc;
+
+use sycamore::prelude::{create_signal, provide_context, use_context, ReadSignal, Signal};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+use super::{use_feedback, Feedback, FeedbackEvent};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Error,
+}
+
+#[derive(Clone)]
+pub struct Toast {
+    id: u32,
+    pub kind: ToastKind,
+    pub message: String,
+    pub action: Option<(&'static str, Rc<dyn Fn()>)>,
+}
+
+impl Toast {
+    fn new(kind: ToastKind, message: impl Into<String>) -> Self {
+        Self {
+            id: 0,
+            kind,
+            message: message.into(),
+            action: None,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(ToastKind::Info, message)
+    }
+
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(ToastKind::Success, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(ToastKind::Error, message)
+    }
+
+    /// Adds a button running `action`, which also dismisses the toast.
+    pub fn action(mut self, label: &'static str, action: impl Fn() + 'static) -> Self {
+        self.action = Some((label, Rc::new(action)));
+        self
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// Handle to show toasts, returned by [`use_toast`].
+#[derive(Clone, Copy)]
+pub struct Toaster {
+    toasts: Signal<Vec<Toast>>,
+    next_id: Signal<u32>,
+    feedback: Feedback,
+}
+
+impl Toaster {
+    /// Milliseconds a toast stays on screen, errors stay twice as long.
+    const DURATION: i32 = 5000;
+    /// Older toasts are dismissed when more are shown at once.
+    const MAX_VISIBLE: usize = 4;
+
+    pub fn show(self, mut toast: Toast) {
+        // Untracked, since toasts are often shown from effects
+        toast.id = self.next_id.get_untracked();
+        self.next_id.set(toast.id + 1);
+
+        let id = toast.id;
+        let (duration, event) = match toast.kind {
+            ToastKind::Error => (Self::DURATION * 2, FeedbackEvent::Error),
+            _ => (Self::DURATION, FeedbackEvent::Notification),
+        };
+        self.feedback.emit(event);
+        self.toasts.update(|toasts| {
+            toasts.push(toast);
+            if toasts.len() > Self::MAX_VISIBLE {
+                toasts.remove(0);
+            }
+        });
+
+        if let Some(window) = web_sys::window() {
+            let dismiss = Closure::once_into_js(move || self.dismiss(id));
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                dismiss.unchecked_ref(),
+                duration,
+            );
+        }
+    }
+
+    pub fn info(self, message: impl Into<String>) {
+        self.show(Toast::info(message));
+    }
+
+    pub fn success(self, message: impl Into<String>) {
+        self.show(Toast::success(message));
+    }
+
+    pub fn error(self, message: impl Into<String>) {
+        self.show(Toast::error(message));
+    }
+
+    pub fn dismiss(self, id: u32) {
+        if self.toasts.is_alive() {
+            self.toasts
+                .update(|toasts| toasts.retain(|toast| toast.id != id));
+        }
+    }
+
+    /// Toasts currently shown, oldest first.
+    pub fn toasts(self) -> ReadSignal<Vec<Toast>> {
+        *self.toasts
+    }
+}
+
+/// Makes a [`Toaster`] available to [`use_toast`] in the current scope.
+/// The toasts are rendered by `ToastStack`.
+///
+/// Must be called after [`super::provide_feedback`].
+pub fn provide_toasts() {
+    provide_context(Toaster {
+        toasts: create_signal(Vec::new()),
+        next_id: create_signal(0),
+        feedback: use_feedback(),
+    });
+}
+
+pub fn use_toast() -> Toaster {
+    use_context()
+}
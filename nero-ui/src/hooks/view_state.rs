@@ -0,0 +1,142 @@
+use std::{any::Any, cell::RefCell, collections::VecDeque};
+
+use js_sys::{Date, Object, Reflect};
+use sycamore::prelude::{create_effect, create_signal, on_cleanup, on_mount, NodeRef, Signal};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::Element;
+
+/// Property of the history state identifying its entry.
+const ENTRY_KEY: &str = "nero.entry";
+/// Values kept at most, the least recently written ones are dropped first.
+const CAPACITY: usize = 100;
+
+thread_local! {
+    /// Values by history entry and name, least recently written first.
+    static STATES: RefCell<VecDeque<((u64, &'static str), Box<dyn Any>)>> =
+        const { RefCell::new(VecDeque::new()) };
+}
+
+fn read<T: Clone + 'static>(entry: u64, name: &'static str) -> Option<T> {
+    STATES.with_borrow(|states| {
+        states
+            .iter()
+            .find(|(key, _)| *key == (entry, name))
+            .and_then(|(_, value)| value.downcast_ref::<T>().cloned())
+    })
+}
+
+fn write<T: 'static>(entry: u64, name: &'static str, value: T) {
+    STATES.with_borrow_mut(|states| {
+        states.retain(|(key, _)| *key != (entry, name));
+        states.push_back(((entry, name), Box::new(value)));
+        if states.len() > CAPACITY {
+            states.pop_front();
+        }
+    });
+}
+
+/// Drops the older half of the values, e.g. when memory runs low. Going back to
+/// their pages loads them again.
+pub(super) fn shrink_view_states() {
+    STATES.with_borrow_mut(|states| {
+        let count = states.len();
+        states.drain(..count / 2);
+    });
+}
+
+/// Id of the current history entry, added to its state the first time.
+fn current_entry() -> Option<u64> {
+    let history = web_sys::window()?.history().ok()?;
+    let state = history.state().ok()?;
+    if let Some(id) = Reflect::get(&state, &ENTRY_KEY.into())
+        .ok()
+        .and_then(|id| id.as_f64())
+    {
+        return Some(id as u64);
+    }
+
+    // Unique across reloads, since the history keeps the states but not the values
+    let id = Date::now() as u64;
+    let state = Object::new();
+    Reflect::set(&state, &ENTRY_KEY.into(), &JsValue::from_f64(id as f64)).ok()?;
+    history.replace_state(&state, "").ok()?;
+    Some(id)
+}
+
+/// UI state of the page shown for the current history entry, kept in memory so
+/// going back to it shows it as it was left, e.g. with the same results loaded.
+///
+/// Pages updating the URL must keep the history state, it identifies the entry.
+#[derive(Clone, Copy)]
+pub struct ViewState {
+    entry: Option<u64>,
+}
+
+impl ViewState {
+    /// Whether a value was saved under `name` for this entry.
+    pub fn contains<T: Clone + 'static>(self, name: &'static str) -> bool {
+        self.entry
+            .and_then(|entry| read::<T>(entry, name))
+            .is_some()
+    }
+
+    /// Creates a signal starting from the value saved under `name` for this entry,
+    /// or `initial`, and saving every change.
+    pub fn signal<T: Clone + 'static>(self, name: &'static str, initial: T) -> Signal<T> {
+        let Some(entry) = self.entry else {
+            return create_signal(initial);
+        };
+
+        let signal = create_signal(read(entry, name).unwrap_or(initial));
+        create_effect(move || signal.with(|value| write(entry, name, value.clone())));
+        signal
+    }
+
+    /// Restores the scroll offset of the element once mounted, and saves it as it scrolls.
+    pub fn scroll(self, name: &'static str, node_ref: NodeRef) {
+        let Some(entry) = self.entry else {
+            return;
+        };
+
+        on_mount(move || {
+            let element: Element = node_ref.get().unchecked_into();
+            if let Some(offset) = read::<i32>(entry, name) {
+                element.set_scroll_top(offset);
+            }
+
+            let listener = Closure::<dyn FnMut()>::new({
+                let element = element.clone();
+                move || write(entry, name, element.scroll_top())
+            });
+            let _ = element
+                .add_event_listener_with_callback("scroll", listener.as_ref().unchecked_ref());
+
+            on_cleanup(move || {
+                let _ = element.remove_event_listener_with_callback(
+                    "scroll",
+                    listener.as_ref().unchecked_ref(),
+                );
+            });
+        });
+    }
+
+    /// Scroll offset saved under `name` for this entry.
+    pub fn scroll_offset(self, name: &'static str) -> Option<i32> {
+        read(self.entry?, name)
+    }
+
+    /// Saves the scroll offset of an element that outlives the page, such as the
+    /// main container of the layout.
+    pub fn set_scroll_offset(self, name: &'static str, offset: i32) {
+        if let Some(entry) = self.entry {
+            write(entry, name, offset);
+        }
+    }
+}
+
+/// Returns the [`ViewState`] of the current history entry.
+pub fn use_view_state() -> ViewState {
+    ViewState {
+        entry: current_entry(),
+    }
+}
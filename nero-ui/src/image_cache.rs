@@ -0,0 +1,129 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use js_sys::{Array, Date};
+use sycamore::prelude::Signal;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, Cache, Request, Response, Url};
+
+/// Browser cache holding the images of `Image::cached` images.
+const CACHE_NAME: &str = "nero-images";
+
+/// Image shown from an object URL, see [`release_object_urls`].
+struct ShownImage {
+    /// Remote URL the object URL was created for.
+    url: String,
+    src: Signal<String>,
+}
+
+thread_local! {
+    /// Milliseconds since the Unix epoch each cached image was last shown, by URL.
+    /// Images cached in earlier sessions aren't listed, so they are evicted first.
+    static LAST_SHOWN: RefCell<HashMap<String, f64>> = RefCell::new(HashMap::new());
+    /// Images shown from the cache, by object URL.
+    static SHOWN: RefCell<HashMap<String, ShownImage>> = RefCell::new(HashMap::new());
+}
+
+/// Returns `url` as the cache lists it in its requests.
+fn normalize(url: &str) -> String {
+    Url::new(url)
+        .map(|url| url.href())
+        .unwrap_or_else(|_| url.to_owned())
+}
+
+async fn open() -> Result<Cache, JsValue> {
+    let window = web_sys::window().ok_or(JsValue::NULL)?;
+    Ok(JsFuture::from(window.caches()?.open(CACHE_NAME))
+        .await?
+        .unchecked_into())
+}
+
+/// Removes the least recently shown images beyond `max_entries`.
+pub async fn evict(max_entries: u32) -> Result<(), JsValue> {
+    let cache = open().await?;
+    let requests: Array = JsFuture::from(cache.keys()).await?.unchecked_into();
+    let excess = requests.length().saturating_sub(max_entries) as usize;
+    if excess == 0 {
+        return Ok(());
+    }
+
+    let mut requests = LAST_SHOWN.with_borrow(|last_shown| {
+        requests
+            .iter()
+            .map(|request| {
+                let request = request.unchecked_into::<Request>();
+                let shown = last_shown.get(&request.url()).copied().unwrap_or_default();
+                (shown, request)
+            })
+            .collect::<Vec<_>>()
+    });
+    // Stable, so images not shown this session stay in insertion order
+    requests.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    for (_, request) in requests.into_iter().take(excess) {
+        JsFuture::from(cache.delete_with_request(&request)).await?;
+        LAST_SHOWN.with_borrow_mut(|last_shown| last_shown.remove(&request.url()));
+    }
+    Ok(())
+}
+
+/// Returns an object URL for `url`, fetching it into the cache if missing.
+///
+/// It is shown by `src` until [`revoke`]d, or [`release_object_urls`] points
+/// `src` back to `url`.
+pub async fn object_url(
+    url: &str,
+    max_entries: u32,
+    src: Signal<String>,
+) -> Result<String, JsValue> {
+    let window = web_sys::window().ok_or(JsValue::NULL)?;
+    let cache = open().await?;
+    LAST_SHOWN.with_borrow_mut(|last_shown| last_shown.insert(normalize(url), Date::now()));
+
+    let cached = JsFuture::from(cache.match_with_str(url)).await?;
+    let response: Response = match cached.is_undefined() {
+        false => cached.unchecked_into(),
+        true => {
+            let response: Response = JsFuture::from(window.fetch_with_str(url))
+                .await?
+                .unchecked_into();
+            if !response.ok() {
+                return Err(JsValue::NULL);
+            }
+            JsFuture::from(cache.put_with_str(url, &response.clone()?)).await?;
+            let _ = evict(max_entries).await;
+            response
+        }
+    };
+
+    let blob: Blob = JsFuture::from(response.blob()?).await?.unchecked_into();
+    let object_url = Url::create_object_url_with_blob(&blob)?;
+    SHOWN.with_borrow_mut(|shown| {
+        shown.insert(
+            object_url.clone(),
+            ShownImage {
+                url: url.to_owned(),
+                src,
+            },
+        )
+    });
+    Ok(object_url)
+}
+
+/// Releases an object URL returned by [`object_url`], once its image is removed.
+pub fn revoke(object_url: &str) {
+    SHOWN.with_borrow_mut(|shown| shown.remove(object_url));
+    let _ = Url::revoke_object_url(object_url);
+}
+
+/// Releases the blobs of the images shown from the cache, e.g. when memory runs
+/// low. The images are shown from their remote URL instead.
+pub fn release_object_urls() {
+    let shown = SHOWN.take();
+    for (object_url, image) in shown {
+        if image.src.is_alive() && image.src.with_untracked(|src| *src == object_url) {
+            image.src.set(image.url);
+        }
+        let _ = Url::revoke_object_url(&object_url);
+    }
+}
@@ -1,14 +1,52 @@
 mod components;
+mod format;
+mod hooks;
+mod image_cache;
 mod macros;
 mod pages;
+mod rich_text;
+mod routes;
+mod search_index;
+mod session;
+mod storage;
+mod stream;
+mod subtitles;
+mod tauri;
 mod types;
 mod utils;
 
-use pages::{BaseLayout, WatchPage};
-use sycamore::render;
+use hooks::{provide_feedback, provide_settings, provide_theme, provide_toasts};
+use pages::BaseLayout;
+use routes::AppRoutes;
+use rustwind::spacing::Padding;
+use sycamore::{
+    prelude::{on_mount, ReadSignal},
+    render,
+    web::{tags::p, GlobalProps, HtmlGlobalAttributes},
+};
+use sycamore_router::{HistoryIntegration, Router, RouterProps};
 
 fn main() {
     console_error_panic_hook::set_once();
 
-    render(|| BaseLayout::new(WatchPage).into())
+    render(|| {
+        // Opening newer data could drop what this version doesn't know about
+        if let Err(error) = storage::migrate() {
+            return p()
+                .class(tw!(Padding::P6))
+                .children(error.to_string())
+                .into();
+        }
+        // Rendering on the migrated data worked, the copy taken before isn't needed
+        on_mount(storage::discard_snapshot);
+
+        provide_settings();
+        provide_theme();
+        provide_feedback();
+        provide_toasts();
+        Router(RouterProps::new(
+            HistoryIntegration::new(),
+            |route: ReadSignal<AppRoutes>| BaseLayout::new(route).into(),
+        ))
+    })
 }
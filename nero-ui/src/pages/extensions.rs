@@ -0,0 +1,39 @@
+use rustwind::{
+    flexbox_grid::{AlignItems, FlexDirection, Gap, JustifyContent},
+    layout::Display,
+    sizing::Height,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::web::{
+    tags::{h1, p, section},
+    GlobalProps, HtmlGlobalAttributes, View,
+};
+
+use crate::tw;
+
+/// Lists the installed extensions, which can't be loaded yet: series come from the
+/// sample extension until the app can run them.
+pub struct ExtensionsPage;
+
+impl From<ExtensionsPage> for View {
+    fn from(_: ExtensionsPage) -> Self {
+        section()
+            .class(tw!(
+                Display::Flex,
+                FlexDirection::Col,
+                AlignItems::Center,
+                JustifyContent::Center,
+                Gap::_4,
+                Height::Full
+            ))
+            .children(
+                h1().class(tw!(FontSize::_3xl, FontWeight::Bold))
+                    .children("Extensions"),
+            )
+            .children(p().class(tw!(TextColor::Gray500)).children(
+                "Installing extensions isn't supported yet, series are loaded from the \
+                 built-in sample extension meanwhile.",
+            ))
+            .into()
+    }
+}
@@ -0,0 +1,185 @@
+use rustwind::{
+    borders::BorderRadius,
+    flexbox_grid::{FlexDirection, FlexWrap, Gap, JustifyContent},
+    layout::{Display, Position, TopRightBottomLeft},
+    sizing::Width,
+    spacing::Padding,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{create_signal, HtmlAAttributes, HtmlInputAttributes, Signal},
+    web::{
+        bind,
+        events::MouseEvent,
+        tags::{a, div, h3, input, p, section},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+
+use crate::{
+    components::{Button, ButtonVariant, Icon, IconType, IntoCard, ListHeader, Modal},
+    format,
+    hooks::use_theme,
+    routes::AppRoutes,
+    storage::{HistoryEntry, WatchHistory},
+    tw,
+};
+
+pub struct HistoryPage;
+
+impl HistoryPage {
+    /// Entries whose series title contains `query`, grouped by the day they were
+    /// watched, most recent first.
+    fn days(entries: &[HistoryEntry], query: &str) -> Vec<(String, Vec<HistoryEntry>)> {
+        let query = query.trim().to_lowercase();
+        let mut days = Vec::<(String, Vec<HistoryEntry>)>::new();
+
+        for entry in entries
+            .iter()
+            .filter(|entry| entry.series_title.to_lowercase().contains(&query))
+        {
+            let day = format::date(entry.watched_at);
+            match days.last_mut() {
+                Some((last, group)) if *last == day => group.push(entry.clone()),
+                _ => days.push((day, vec![entry.clone()])),
+            }
+        }
+        days
+    }
+
+    /// Card linking to the episode, where playback resumes from the saved position.
+    fn item(entry: HistoryEntry, entries: Signal<Vec<HistoryEntry>>) -> View {
+        let extension_id = entry.extension_id.clone();
+        let series_id = entry.series_id.clone();
+        let episode_id = entry.episode_id.clone();
+        let remove = move |_: MouseEvent| {
+            WatchHistory::remove(&extension_id, &series_id, &episode_id);
+            entries.set(WatchHistory::entries());
+        };
+
+        div()
+            .class(tw!(Position::Relative))
+            .children(
+                a().href(AppRoutes::watch_episode(
+                    &entry.series_id,
+                    entry.episode_number,
+                ))
+                .children(entry.into_card()),
+            )
+            .children(
+                div()
+                    .class(tw!(
+                        Position::Absolute,
+                        TopRightBottomLeft::Top2,
+                        TopRightBottomLeft::Right2
+                    ))
+                    .children(
+                        Button::icon(Icon::new(IconType::Close), remove)
+                            .color(use_theme().get().surface()),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl From<HistoryPage> for View {
+    fn from(_: HistoryPage) -> Self {
+        let entries = create_signal(WatchHistory::entries());
+        let query = create_signal(String::new());
+        let confirm_clear = create_signal(false);
+        let theme = use_theme();
+
+        let days = move || {
+            let days =
+                entries.with(|entries| query.with(|query| HistoryPage::days(entries, query)));
+
+            let content: View = match days.is_empty() {
+                true => p()
+                    .class(tw!(TextColor::Gray500))
+                    .children(match entries.with(Vec::is_empty) {
+                        true => "Episodes you watch will show up here.",
+                        false => "No series match your search.",
+                    })
+                    .into(),
+                false => days
+                    .into_iter()
+                    .map(|(day, group)| {
+                        section()
+                            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
+                            .children(
+                                h3().class(tw!(FontWeight::Semibold, TextColor::Gray500))
+                                    .children(day),
+                            )
+                            .children(
+                                div()
+                                    .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_4))
+                                    .children(
+                                        group
+                                            .into_iter()
+                                            .map(|entry| HistoryPage::item(entry, entries))
+                                            .collect::<Vec<_>>(),
+                                    ),
+                            )
+                            .into()
+                    })
+                    .collect::<Vec<View>>()
+                    .into(),
+            };
+            content
+        };
+
+        section()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_4))
+            .children(ListHeader::new("History").sticky(false))
+            .children(
+                div()
+                    .class(tw!(Display::Flex, JustifyContent::Between, Gap::_2))
+                    .children(
+                        input()
+                            .class(move || {
+                                format!(
+                                    "{} {}",
+                                    tw!(
+                                        Width::_1over3,
+                                        Padding::Px3,
+                                        Padding::Py1_5,
+                                        BorderRadius::Lg,
+                                        FontSize::Sm
+                                    ),
+                                    theme.get().control()
+                                )
+                            })
+                            .r#type("search")
+                            .placeholder("Search series")
+                            .bind(bind::value, query),
+                    )
+                    .children(
+                        Button::label("Clear history", move |_| confirm_clear.set(true))
+                            .variant(ButtonVariant::Danger)
+                            .disabled(move || entries.with(Vec::is_empty)),
+                    ),
+            )
+            .children(
+                Modal::new(
+                    confirm_clear,
+                    (
+                        p().children("Remove every episode from your watch history?"),
+                        div()
+                            .class(tw!(Display::Flex, JustifyContent::End, Gap::_2))
+                            .children(Button::label("Cancel", move |_| confirm_clear.set(false)))
+                            .children(
+                                Button::label("Clear", move |_| {
+                                    confirm_clear.set(false);
+                                    WatchHistory::clear();
+                                    entries.set(Vec::new());
+                                })
+                                .variant(ButtonVariant::Danger),
+                            ),
+                    ),
+                )
+                .title("Clear history"),
+            )
+            .children(days)
+            .into()
+    }
+}
@@ -0,0 +1,50 @@
+use rustwind::{
+    flexbox_grid::{FlexDirection, Gap},
+    layout::{Display, Overflow},
+    spacing::Padding,
+    typography::TextColor,
+};
+use sycamore::web::{
+    tags::{div, p, section},
+    GlobalProps, HtmlGlobalAttributes, View,
+};
+
+use crate::{
+    components::{IntoCard, ListHeader},
+    storage::WatchHistory,
+    tw,
+};
+
+pub struct HomePage;
+
+impl HomePage {
+    const CONTINUE_WATCHING_LIMIT: usize = 12;
+}
+
+impl From<HomePage> for View {
+    fn from(_: HomePage) -> Self {
+        let history = WatchHistory::recent_series(HomePage::CONTINUE_WATCHING_LIMIT);
+
+        let carousel: View = match history.is_empty() {
+            true => p()
+                .class(tw!(TextColor::Gray500))
+                .children("Series you start watching will show up here.")
+                .into(),
+            false => div()
+                .class(tw!(Display::Flex, Gap::_4, Overflow::XAuto, Padding::Pb2))
+                .children(
+                    history
+                        .into_iter()
+                        .map(|entry| entry.into_card().into())
+                        .collect::<Vec<View>>(),
+                )
+                .into(),
+        };
+
+        section()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_4))
+            .children(ListHeader::new("Continue watching").sticky(false))
+            .children(carousel)
+            .into()
+    }
+}
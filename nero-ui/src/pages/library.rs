@@ -0,0 +1,343 @@
+use std::collections::{HashMap, HashSet};
+
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    flexbox_grid::{FlexDirection, FlexWrap, Gap, GridTemplateColumns, JustifyContent},
+    layout::{Display, Position, TopRightBottomLeft},
+    sizing::Width,
+    spacing::Padding,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{create_memo, create_signal, HtmlInputAttributes, Signal},
+    web::{
+        bind,
+        events::{click, MouseEvent},
+        tags::{button, div, input, p, section, span},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+
+use crate::{
+    components::{Button, ButtonVariant, IntoCard, ListHeader, Modal},
+    format,
+    hooks::use_theme,
+    search_index::SearchIndex,
+    storage::{Bookmark, Library, WatchHistory},
+    tw,
+    types::Series,
+    utils::ViewBuilder,
+};
+
+pub struct LibraryPage;
+
+impl LibraryPage {
+    fn chip(label: String, selected: bool, mut on_click: impl FnMut() + 'static) -> View {
+        let theme = use_theme();
+
+        button()
+            .class(move || {
+                format!(
+                    "{} {}",
+                    tw!(Padding::Px3, Padding::Py1, BorderRadius::Full, FontSize::Sm),
+                    match selected {
+                        true => tw!(BackgroundColor::Red300, TextColor::Black),
+                        false => theme.get().control(),
+                    }
+                )
+            })
+            .children(label)
+            .on(click, move |_| on_click())
+            .into()
+    }
+
+    /// Label over a corner of the poster, `side` being `Left2` or `Right2`.
+    fn badge(label: String, color: BackgroundColor, side: TopRightBottomLeft) -> View {
+        span()
+            .class(format!(
+                "{} {} {}",
+                tw!(
+                    Position::Absolute,
+                    TopRightBottomLeft::Top2,
+                    Padding::Px2,
+                    Padding::Py0_5,
+                    BorderRadius::Full,
+                    FontSize::Xs,
+                    FontWeight::Semibold,
+                    TextColor::White
+                ),
+                color.as_class(),
+                side.as_class()
+            ))
+            .children(label)
+            .into()
+    }
+
+    /// Poster card of the bookmark, followed by chips to (un)assign its collections.
+    ///
+    /// `history_finished` are the episodes the history shows as finished, by extension
+    /// and series, which bookmarks saved before they were watched may not know of.
+    fn bookmark_card(
+        bookmark: Bookmark,
+        bookmarks: Signal<Vec<Bookmark>>,
+        history_finished: &HashMap<(String, String), HashSet<String>>,
+    ) -> View {
+        let finished = history_finished
+            .get(&(bookmark.extension_id.clone(), bookmark.series_id.clone()))
+            .into_iter()
+            .flatten()
+            .chain(&bookmark.finished_episodes)
+            .collect::<HashSet<_>>()
+            .len();
+        let unwatched = bookmark
+            .episode_count
+            .map(|count| usize::from(count).saturating_sub(finished))
+            .filter(|&count| count > 0);
+
+        let chips = Library::collections()
+            .into_iter()
+            .map(|collection| {
+                let selected = bookmark.collections.contains(&collection);
+                let extension_id = bookmark.extension_id.clone();
+                let series_id = bookmark.series_id.clone();
+
+                Self::chip(collection.clone(), selected, move || {
+                    Library::toggle_collection(&extension_id, &series_id, &collection);
+                    bookmarks.set(Library::bookmarks());
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let series = Series {
+            id: bookmark.series_id,
+            title: bookmark.title,
+            alternative_titles: bookmark.alternative_titles,
+            genres: bookmark.genres,
+            poster_url: bookmark.poster_url,
+            synopsis: None,
+            r#type: None,
+            trailer_url: None,
+        };
+
+        div()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
+            .children(
+                div()
+                    .class(tw!(Position::Relative))
+                    .children(series.into_card())
+                    .when_some(unwatched, |this, count| {
+                        this.children(Self::badge(
+                            format!("{} unwatched", format::compact(count)),
+                            BackgroundColor::Gray900,
+                            TopRightBottomLeft::Right2,
+                        ))
+                    }),
+            )
+            .children(
+                div()
+                    .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_1))
+                    .children(chips),
+            )
+            .into()
+    }
+}
+
+impl From<LibraryPage> for View {
+    fn from(_: LibraryPage) -> Self {
+        let bookmarks = create_signal(Library::bookmarks());
+        let collections = create_signal(Library::collections());
+        // Read once for every card, the history doesn't change while the page is open
+        let history_finished = WatchHistory::finished_episodes();
+        let selected = create_signal(None::<String>);
+        let new_collection = create_signal(String::new());
+        let confirm_remove = create_signal(false);
+        let query = create_signal(String::new());
+        let index = create_memo(move || {
+            bookmarks.with(|bookmarks| {
+                SearchIndex::new(bookmarks.iter().map(|bookmark| {
+                    std::iter::once(&bookmark.title)
+                        .chain(&bookmark.alternative_titles)
+                        .chain(&bookmark.genres)
+                }))
+            })
+        });
+        let theme = use_theme();
+
+        let collection_chips = move || {
+            let all = LibraryPage::chip(
+                format!(
+                    "All · {}",
+                    bookmarks.with(|bookmarks| format::compact(bookmarks.len()))
+                ),
+                selected.with(Option::is_none),
+                move || selected.set(None),
+            );
+
+            let chips = collections.get_clone().into_iter().map(|collection| {
+                let is_selected = selected.with(|s| s.as_ref() == Some(&collection));
+                let count = bookmarks.with(|bookmarks| {
+                    bookmarks
+                        .iter()
+                        .filter(|bookmark| bookmark.collections.contains(&collection))
+                        .count()
+                });
+                let label = format!("{collection} · {}", format::compact(count));
+
+                LibraryPage::chip(label, is_selected, move || {
+                    selected.set(Some(collection.clone()))
+                })
+            });
+
+            std::iter::once(all).chain(chips).collect::<Vec<_>>()
+        };
+
+        let create_collection = move |_: MouseEvent| {
+            let name = new_collection.get_clone();
+            let name = name.trim();
+            if name.is_empty() {
+                return;
+            }
+
+            Library::create_collection(name);
+            collections.set(Library::collections());
+            bookmarks.set(Library::bookmarks());
+            new_collection.set(String::new());
+        };
+
+        let remove_collection = move |_: MouseEvent| {
+            confirm_remove.set(false);
+            if let Some(name) = selected.get_clone() {
+                Library::remove_collection(&name);
+                selected.set(None);
+                collections.set(Library::collections());
+                bookmarks.set(Library::bookmarks());
+            }
+        };
+
+        section()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_4))
+            .children(ListHeader::new("Library").sticky(false))
+            .children(
+                input()
+                    .class(move || {
+                        format!(
+                            "{} {}",
+                            tw!(
+                                Width::_1over3,
+                                Padding::Px3,
+                                Padding::Py1_5,
+                                BorderRadius::Lg,
+                                FontSize::Sm
+                            ),
+                            theme.get().control()
+                        )
+                    })
+                    .r#type("search")
+                    .placeholder("Search titles and genres")
+                    .bind(bind::value, query),
+            )
+            .children(
+                div()
+                    .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_2))
+                    .children(collection_chips),
+            )
+            .children(
+                div()
+                    .class(tw!(Display::Flex, Gap::_2))
+                    .children(
+                        input()
+                            .class(move || {
+                                format!(
+                                    "{} {}",
+                                    tw!(Padding::Px3, Padding::Py1_5, BorderRadius::Lg),
+                                    theme.get().control()
+                                )
+                            })
+                            .placeholder("New collection")
+                            .bind(bind::value, new_collection),
+                    )
+                    .children(
+                        Button::label("Create", create_collection)
+                            .variant(ButtonVariant::Primary)
+                            .disabled(move || new_collection.with(|name| name.trim().is_empty())),
+                    )
+                    .children(move || match selected.with(Option::is_some) {
+                        true => {
+                            Button::label("Delete collection", move |_| confirm_remove.set(true))
+                                .color(BackgroundColor::Gray500)
+                                .into()
+                        }
+                        false => View::default(),
+                    }),
+            )
+            .children(
+                Modal::new(
+                    confirm_remove,
+                    (
+                        p().children(move || {
+                            format!(
+                                "Delete the \"{}\" collection? Its series stay in your library.",
+                                selected.get_clone().unwrap_or_default()
+                            )
+                        }),
+                        div()
+                            .class(tw!(Display::Flex, JustifyContent::End, Gap::_2))
+                            .children(Button::label("Cancel", move |_| confirm_remove.set(false)))
+                            .children(
+                                Button::label("Delete", remove_collection)
+                                    .variant(ButtonVariant::Danger),
+                            ),
+                    ),
+                )
+                .title("Delete collection"),
+            )
+            .children(move || -> View {
+                let searching = query.with(|query| !query.trim().is_empty());
+                let visible = bookmarks.with(|bookmarks| {
+                    // Best matches first while searching
+                    let order = match searching {
+                        true => index.with(|index| query.with(|query| index.search(query))),
+                        false => (0..bookmarks.len()).collect(),
+                    };
+                    order
+                        .into_iter()
+                        .map(|position| &bookmarks[position])
+                        .filter(|bookmark| {
+                            selected.with(|selected| match selected {
+                                Some(collection) => bookmark.collections.contains(collection),
+                                None => true,
+                            })
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>()
+                });
+
+                match visible.is_empty() {
+                    true => p()
+                        .class(tw!(TextColor::Gray500))
+                        .children(match searching {
+                            true => "No series match your search.",
+                            false => "Series you bookmark will show up here.",
+                        })
+                        .into(),
+                    false => div()
+                        .class(tw!(Display::Grid, GridTemplateColumns::_4, Gap::_6))
+                        .children(
+                            visible
+                                .into_iter()
+                                .map(|bookmark| {
+                                    LibraryPage::bookmark_card(
+                                        bookmark,
+                                        bookmarks,
+                                        &history_finished,
+                                    )
+                                })
+                                .collect::<Vec<_>>(),
+                        )
+                        .into(),
+                }
+            })
+            .into()
+    }
+}
@@ -1,9 +1,21 @@
+mod extensions;
+mod history;
+mod home;
+mod library;
+mod not_found;
+mod search;
 mod series;
+mod settings;
 mod watch;
 
-// Marked as unused until router is created
-#[allow(unused_imports)]
+pub use extensions::*;
+pub use history::*;
+pub use home::*;
+pub use library::*;
+pub use not_found::*;
+pub use search::*;
 pub use series::*;
+pub use settings::*;
 pub use watch::*;
 
 use rustwind::{
@@ -11,55 +23,163 @@ use rustwind::{
     layout::{Display, Overflow, Position},
     sizing::{Height, Width},
     spacing::Padding,
+    typography::FontFamily,
 };
-use sycamore::web::{
-    tags::{article, aside, div, figure, main},
-    GlobalProps, HtmlGlobalAttributes, View,
+use sycamore::{
+    prelude::{create_effect, create_node_ref, on_mount, ReadSignal},
+    web::{
+        events::{scroll, Event},
+        tags::{article, aside, div, figure, main},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
 };
+use sycamore_router::navigate_replace;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::Element;
 
-use crate::{components::Toolbar, tw};
+use crate::{
+    components::{FeedbackFlash, NavRail, ToastStack, Toolbar},
+    hooks::{
+        provide_app_state, use_app_state, use_media_query, use_settings, use_theme, use_view_state,
+    },
+    routes::AppRoutes,
+    storage::{Font, StartPage, WatchHistory},
+    tw,
+};
 
+/// Layout around every page, showing the page of `route` next to the navigation rail.
 pub struct BaseLayout {
-    children: View,
+    route: ReadSignal<AppRoutes>,
 }
 
 impl BaseLayout {
-    pub fn new(children: impl Into<View>) -> Self {
-        Self {
-            children: children.into(),
+    pub fn new(route: ReadSignal<AppRoutes>) -> Self {
+        Self { route }
+    }
+
+    /// Class of the font family, inherited by every element of the page.
+    fn font_class(font: Font) -> &'static str {
+        match font {
+            Font::System => tw!(FontFamily::Sans),
+            Font::Serif => tw!(FontFamily::Serif),
+            // Custom family of `tailwind.config.js`, which rustwind can't name
+            Font::Dyslexic => "font-dyslexic",
+        }
+    }
+
+    /// Path of the start page, if it isn't the home page.
+    fn start_path(start_page: StartPage, extension_id: &str) -> Option<String> {
+        match start_page {
+            StartPage::Home => None,
+            StartPage::Library => Some(AppRoutes::Library.path().to_owned()),
+            StartPage::ContinueWatching => WatchHistory::entries()
+                .into_iter()
+                .find(|entry| entry.extension_id == extension_id)
+                .map(|entry| AppRoutes::watch_episode(&entry.series_id, entry.episode_number)),
         }
     }
 }
 
 impl From<BaseLayout> for View {
     fn from(layout: BaseLayout) -> Self {
+        provide_app_state();
+        let route = layout.route;
+        let theme = use_theme();
+        let settings = use_settings();
+        let main_ref = create_node_ref();
+
+        // Opening the app at its root shows the start page, other paths (e.g. after a
+        // reload) are kept
+        on_mount(move || {
+            if route.get_untracked() != AppRoutes::Home {
+                return;
+            }
+            let start_page = settings.with_untracked(|settings| settings.start_page);
+            let extension_id = use_app_state().extension_id.get_clone_untracked();
+            if let Some(path) = BaseLayout::start_path(start_page, &extension_id) {
+                navigate_replace(&path);
+            }
+        });
+
+        // Pages scroll within `main`, which isn't recreated with them: going back to a
+        // page restores its offset, new pages start from the top
+        on_mount(move || {
+            create_effect(move || {
+                route.track();
+                // Deferred until the router updated the history and rendered the page
+                spawn_local(async move {
+                    let offset = use_view_state().scroll_offset("main").unwrap_or_default();
+                    main_ref
+                        .get()
+                        .unchecked_into::<Element>()
+                        .set_scroll_top(offset);
+                });
+            });
+        });
+
         div()
-            .class(tw!(
-                Position::Fixed,
-                Display::Flex,
-                FlexDirection::Col,
-                Height::Screen,
-                Width::Full,
-                Gap::_4,
-                Padding::Px12,
-                Padding::Pt4
-            ))
-            .children(Toolbar)
+            .class(move || {
+                format!(
+                    "{} {} {}",
+                    tw!(
+                        Position::Fixed,
+                        Display::Flex,
+                        Height::Screen,
+                        Width::Full,
+                        Gap::_8,
+                        Padding::Pl4,
+                        Padding::Pr12
+                    ),
+                    theme.get().page(),
+                    BaseLayout::font_class(settings.with(|settings| settings.font))
+                )
+            })
+            .children(NavRail::new(route))
             .children(
-                main()
-                    .class(tw!(Height::Full, Flex::_1, Overflow::Auto))
-                    .children(layout.children),
+                div()
+                    .class(tw!(
+                        Display::Flex,
+                        FlexDirection::Col,
+                        Flex::_1,
+                        Gap::_4,
+                        Padding::Pt4
+                    ))
+                    .children(Toolbar)
+                    .children(
+                        main()
+                            .r#ref(main_ref)
+                            .class(tw!(Height::Full, Flex::_1, Overflow::Auto))
+                            .on(scroll, move |event: Event| {
+                                let main: Element = event.target().unwrap_throw().unchecked_into();
+                                use_view_state().set_scroll_offset("main", main.scroll_top());
+                            })
+                            .children(move || route.get().view()),
+                    ),
             )
+            .children(ToastStack)
+            .children(FeedbackFlash)
             .into()
     }
 }
 
 pub enum SplitLayout {
-    Default { left: View, right: View },
-    Watch { left: View, right: View },
+    Default {
+        left: View,
+        right: View,
+    },
+    Watch {
+        left: View,
+        right: View,
+        /// Hides the aside while `true`, e.g. while the player is fullscreen.
+        collapsed: Option<ReadSignal<bool>>,
+    },
 }
 
 impl SplitLayout {
+    /// Viewports where the watch layout fits the player and the episodes side by side.
+    const WIDE_QUERY: &str = "(min-width: 1024px)";
+
     pub fn new_default(figure: impl Into<View>, article: impl Into<View>) -> Self {
         Self::Default {
             left: figure.into(),
@@ -71,6 +191,19 @@ impl SplitLayout {
         Self::Watch {
             left: article.into(),
             right: aside.into(),
+            collapsed: None,
+        }
+    }
+
+    /// Hides the aside of the watch layout while `collapsed` is `true`.
+    pub fn collapsed(self, collapsed: ReadSignal<bool>) -> Self {
+        match self {
+            Self::Watch { left, right, .. } => Self::Watch {
+                left,
+                right,
+                collapsed: Some(collapsed),
+            },
+            layout => layout,
         }
     }
 }
@@ -96,23 +229,45 @@ impl From<SplitLayout> for View {
                         ))
                         .children(right),
                 ),
-            SplitLayout::Watch { left, right } => div()
-                .class(tw!(Display::Flex, Height::Full, Gap::_12, Overflow::Hidden))
-                .children(
-                    article()
-                        .class(tw!(
-                            Display::Flex,
-                            FlexDirection::Col,
-                            Width::_4over6,
-                            Gap::_4
-                        ))
-                        .children(left),
-                )
-                .children(
-                    aside()
-                        .class(tw!(Width::_2over6, Overflow::YAuto))
-                        .children(right),
-                ),
+            SplitLayout::Watch {
+                left,
+                right,
+                collapsed,
+            } => {
+                let wide = use_media_query(SplitLayout::WIDE_QUERY);
+                let is_collapsed = move || collapsed.is_some_and(|collapsed| collapsed.get());
+
+                // Below the breakpoint the episodes are stacked under the player
+                div()
+                    .class(move || match wide.get() {
+                        true => tw!(Display::Flex, Height::Full, Gap::_12, Overflow::Hidden),
+                        false => tw!(Display::Flex, FlexDirection::Col, Gap::_8),
+                    })
+                    .children(
+                        article()
+                            .class(move || {
+                                let width = match wide.get() && !is_collapsed() {
+                                    true => Width::_4over6,
+                                    false => Width::Full,
+                                };
+                                format!(
+                                    "{} {}",
+                                    tw!(Display::Flex, FlexDirection::Col, Gap::_4),
+                                    width.as_class()
+                                )
+                            })
+                            .children(left),
+                    )
+                    .children(
+                        aside()
+                            .class(move || match (is_collapsed(), wide.get()) {
+                                (true, _) => tw!(Display::Hidden),
+                                (false, true) => tw!(Width::_2over6, Overflow::YAuto),
+                                (false, false) => tw!(Width::Full),
+                            })
+                            .children(right),
+                    )
+            }
         }
         .into()
     }
@@ -0,0 +1,51 @@
+use rustwind::{
+    flexbox_grid::{AlignItems, FlexDirection, Gap, JustifyContent},
+    layout::Display,
+    sizing::Height,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::web::{
+    tags::{h1, p, section},
+    GlobalProps, HtmlGlobalAttributes, View,
+};
+use sycamore_router::navigate;
+
+use crate::{
+    components::{Button, ButtonVariant},
+    routes::AppRoutes,
+    tw,
+};
+
+/// Shown for paths that don't match any route.
+pub struct NotFoundPage;
+
+impl From<NotFoundPage> for View {
+    fn from(_: NotFoundPage) -> Self {
+        let path = web_sys::window()
+            .and_then(|window| window.location().pathname().ok())
+            .unwrap_or_default();
+
+        section()
+            .class(tw!(
+                Display::Flex,
+                FlexDirection::Col,
+                AlignItems::Center,
+                JustifyContent::Center,
+                Gap::_4,
+                Height::Full
+            ))
+            .children(
+                h1().class(tw!(FontSize::_3xl, FontWeight::Bold))
+                    .children("Page not found"),
+            )
+            .children(
+                p().class(tw!(TextColor::Gray500))
+                    .children(format!("Nothing lives at {path}, it may have moved.")),
+            )
+            .children(
+                Button::label("Back to home", |_| navigate(AppRoutes::Home.path()))
+                    .variant(ButtonVariant::Primary),
+            )
+            .into()
+    }
+}
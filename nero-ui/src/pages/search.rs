@@ -0,0 +1,431 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    flexbox_grid::{AlignItems, Flex, FlexDirection, FlexWrap, Gap, GridTemplateColumns},
+    layout::{Display, Overflow},
+    sizing::{Height, Width},
+    spacing::Padding,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{
+        create_effect, create_memo, create_node_ref, create_signal, HtmlInputAttributes, Signal,
+    },
+    web::{
+        bind,
+        events::{change, click, scroll, Event},
+        tags::{aside, button, div, h3, input, label, p, section, span},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Element, HtmlInputElement, UrlSearchParams};
+
+use crate::{
+    components::{Button, Icon, IconType, IntoCard, ListHeader, MultiSelect, Skeleton},
+    hooks::{use_theme, use_view_state},
+    tw,
+    types::{FilterCategory, FilterKind, FilterValue, SearchFilter, Series, SeriesPage},
+    utils::ViewBuilder,
+};
+
+pub struct SearchPage;
+
+impl SearchPage {
+    /// Distance in pixels from the bottom of the results at which the next page is requested.
+    const SCROLL_THRESHOLD: i32 = 200;
+    const SAMPLE_PAGES: u16 = 3;
+    /// Columns of the results grid, a row of placeholders is shown for the next page.
+    const COLUMNS: usize = 4;
+
+    /// Reads the initial query from the `q` parameter of the current URL.
+    fn initial_query() -> String {
+        web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
+            .and_then(|params| params.get("q"))
+            .unwrap_or_default()
+    }
+
+    /// Mirrors the query in the URL so the search can be reloaded or shared.
+    fn sync_query(query: &str) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let url = match query.is_empty() {
+            true => window.location().pathname().unwrap_or_default(),
+            false => format!("?q={}", js_sys::encode_uri_component(query)),
+        };
+        let _ = window
+            .history()
+            .and_then(|history| history.replace_state_with_url(&history.state()?, "", Some(&url)));
+    }
+
+    // TODO: search through the selected extension once they can be loaded
+    fn search(query: &str, page: u16, _filters: &[SearchFilter]) -> SeriesPage {
+        let query = query.to_lowercase();
+        let mut series_page = SeriesPage::default();
+
+        series_page
+            .series
+            .retain(|series| series.title.to_lowercase().contains(&query));
+        series_page.has_next_page = !series_page.series.is_empty() && page < Self::SAMPLE_PAGES;
+        series_page
+    }
+
+    fn selected_values(filters: &[SearchFilter], id: &str) -> Vec<String> {
+        filters
+            .iter()
+            .find_map(|(filter_id, value)| match value {
+                FilterValue::Selected(values) if filter_id == id => Some(values.clone()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// Replaces the value of the filter `id`, removing it when `value` is `None`.
+    fn set_filter(filters: &mut Vec<SearchFilter>, id: &str, value: Option<FilterValue>) {
+        filters.retain(|(filter_id, _)| filter_id != id);
+        if let Some(value) = value {
+            filters.push((id.to_owned(), value));
+        }
+    }
+
+    fn input_element(event: &Event) -> HtmlInputElement {
+        event.target().unwrap_throw().unchecked_into()
+    }
+
+    fn select_chips(category: FilterCategory, selected: Signal<Vec<SearchFilter>>) -> View {
+        let theme = use_theme();
+        let single = matches!(category.kind, FilterKind::SingleSelect);
+
+        let chips = category
+            .filters
+            .into_iter()
+            .map(|(name, value)| {
+                let (id, chip_value) = (category.id.clone(), value.clone());
+                let chip_id = id.clone();
+
+                button()
+                    .class(move || {
+                        let base =
+                            tw!(Padding::Px3, Padding::Py1, BorderRadius::Full, FontSize::Sm);
+                        let color = match selected
+                            .with(|filters| Self::selected_values(filters, &id).contains(&value))
+                        {
+                            true => tw!(BackgroundColor::Red300, TextColor::Black),
+                            false => theme.get().control(),
+                        };
+                        format!("{base} {color}")
+                    })
+                    .children(name)
+                    .on(click, move |_| {
+                        selected.update(|filters| {
+                            let mut values = Self::selected_values(filters, &chip_id);
+                            match values.iter().position(|v| *v == chip_value) {
+                                Some(index) => {
+                                    values.remove(index);
+                                }
+                                None if single => values = vec![chip_value.clone()],
+                                None => values.push(chip_value.clone()),
+                            }
+                            Self::set_filter(
+                                filters,
+                                &chip_id,
+                                (!values.is_empty()).then_some(FilterValue::Selected(values)),
+                            )
+                        })
+                    })
+                    .into()
+            })
+            .collect::<Vec<View>>();
+
+        div()
+            .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_2))
+            .children(chips)
+            .into()
+    }
+
+    fn multi_select(category: FilterCategory, selected: Signal<Vec<SearchFilter>>) -> View {
+        let id = category.id;
+        let values = create_memo({
+            let id = id.clone();
+            move || selected.with(|filters| Self::selected_values(filters, &id))
+        });
+
+        MultiSelect::new(category.filters, values, move |values| {
+            selected.update(|filters| {
+                Self::set_filter(
+                    filters,
+                    &id,
+                    (!values.is_empty()).then_some(FilterValue::Selected(values)),
+                )
+            })
+        })
+        .placeholder("Type to filter")
+        .into()
+    }
+
+    fn range_input(
+        id: String,
+        bounds: (i32, i32),
+        range: Signal<(i32, i32)>,
+        is_start: bool,
+        selected: Signal<Vec<SearchFilter>>,
+    ) -> View {
+        let placeholder = if is_start { bounds.0 } else { bounds.1 };
+        let theme = use_theme();
+
+        input()
+            .class(move || {
+                format!(
+                    "{} {}",
+                    tw!(Width::Full, Padding::Px3, Padding::Py1_5, BorderRadius::Lg),
+                    theme.get().control()
+                )
+            })
+            .r#type("number")
+            .placeholder(placeholder.to_string())
+            .on(change, move |event: Event| {
+                let input = Self::input_element(&event);
+                let value = input.value().parse::<i32>().unwrap_or(placeholder);
+
+                // Each end is kept within the bounds and the other end, so the
+                // range can't be inverted
+                range.update(|(start, end)| match is_start {
+                    true => *start = value.clamp(bounds.0, *end),
+                    false => *end = value.clamp(*start, bounds.1),
+                });
+
+                let (start, end) = range.get();
+                if !input.value().is_empty() {
+                    input.set_value(&if is_start { start } else { end }.to_string());
+                }
+                selected.update(|filters| {
+                    Self::set_filter(
+                        filters,
+                        &id,
+                        ((start, end) != bounds).then_some(FilterValue::Range(start, end)),
+                    )
+                })
+            })
+            .into()
+    }
+
+    fn filter_control(category: FilterCategory, selected: Signal<Vec<SearchFilter>>) -> View {
+        let id = category.id.clone();
+        let theme = use_theme();
+        let title = h3()
+            .class(tw!(FontWeight::Semibold))
+            .children(category.display_name.clone());
+
+        let control: View = match category.kind {
+            FilterKind::MultiSelect => (title, Self::multi_select(category, selected)).into(),
+            FilterKind::SingleSelect => (title, Self::select_chips(category, selected)).into(),
+            FilterKind::Toggle => label()
+                .class(tw!(Display::Flex, AlignItems::Center, Gap::_2))
+                .children(input().r#type("checkbox").on(change, move |event: Event| {
+                    let checked = Self::input_element(&event).checked();
+                    selected.update(|filters| {
+                        Self::set_filter(filters, &id, checked.then_some(FilterValue::Toggle(true)))
+                    })
+                }))
+                .children(
+                    span()
+                        .class(tw!(FontWeight::Semibold))
+                        .children(category.display_name),
+                )
+                .into(),
+            FilterKind::Range(min, max) => {
+                let range = create_signal((min, max));
+
+                (
+                    title,
+                    div()
+                        .class(tw!(Display::Flex, AlignItems::Center, Gap::_2))
+                        .children(Self::range_input(
+                            id.clone(),
+                            (min, max),
+                            range,
+                            true,
+                            selected,
+                        ))
+                        .children(span().children("–"))
+                        .children(Self::range_input(id, (min, max), range, false, selected)),
+                )
+                    .into()
+            }
+            FilterKind::Text => (
+                title,
+                input()
+                    .class(move || {
+                        format!(
+                            "{} {}",
+                            tw!(Width::Full, Padding::Px3, Padding::Py1_5, BorderRadius::Lg),
+                            theme.get().control()
+                        )
+                    })
+                    .r#type("text")
+                    .on(change, move |event: Event| {
+                        let text = Self::input_element(&event).value();
+                        selected.update(|filters| {
+                            Self::set_filter(
+                                filters,
+                                &id,
+                                (!text.is_empty()).then_some(FilterValue::Text(text)),
+                            )
+                        })
+                    }),
+            )
+                .into(),
+        };
+
+        div()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
+            .children(control)
+            .into()
+    }
+
+    fn filter_sidebar(selected: Signal<Vec<SearchFilter>>) -> View {
+        FilterCategory::samples()
+            .into_iter()
+            .map(|category| Self::filter_control(category, selected))
+            .collect::<Vec<View>>()
+            .into()
+    }
+}
+
+impl From<SearchPage> for View {
+    fn from(_: SearchPage) -> Self {
+        let view_state = use_view_state();
+        let theme = use_theme();
+        // Coming back from a series shows the results and the scroll as they were left
+        let restored = view_state.contains::<Vec<Series>>("search.results");
+        let query = create_signal(SearchPage::initial_query());
+        let selected = view_state.signal("search.filters", Vec::<SearchFilter>::new());
+        let sidebar_open = create_signal(true);
+        let results = view_state.signal("search.results", Vec::<Series>::new());
+        let page = view_state.signal("search.page", 1u16);
+        let has_next_page = view_state.signal("search.has_next_page", false);
+        let results_ref = create_node_ref();
+        view_state.scroll("search.results", results_ref);
+
+        let load_page = move |page_number: u16| {
+            let series_page = SearchPage::search(
+                &query.get_clone_untracked(),
+                page_number,
+                &selected.get_clone_untracked(),
+            );
+            results.update(|results| results.extend(series_page.series));
+            has_next_page.set(series_page.has_next_page);
+            page.set(page_number);
+        };
+
+        // Restart the search from the first page whenever the query or the filters change
+        let mut skip_search = restored;
+        create_effect(move || {
+            query.track();
+            selected.track();
+
+            SearchPage::sync_query(&query.get_clone_untracked());
+            // The restored results are already those of the query and the filters
+            if std::mem::take(&mut skip_search) {
+                return;
+            }
+            results.set(Vec::new());
+            load_page(1);
+        });
+
+        div()
+            .class(tw!(Display::Flex, Height::Full, Gap::_8))
+            .children(move || match sidebar_open.get() {
+                true => aside()
+                    .class(tw!(
+                        Display::Flex,
+                        FlexDirection::Col,
+                        Width::_1over4,
+                        Gap::_4,
+                        Overflow::YAuto
+                    ))
+                    .children(ListHeader::new("Filters").sticky(false))
+                    .children(SearchPage::filter_sidebar(selected))
+                    .into(),
+                false => View::default(),
+            })
+            .children(
+                section()
+                    .r#ref(results_ref)
+                    .class(tw!(
+                        Display::Flex,
+                        FlexDirection::Col,
+                        Flex::_1,
+                        Gap::_4,
+                        Overflow::YAuto
+                    ))
+                    .on(scroll, move |event: Event| {
+                        let element: Element = event.target().unwrap_throw().unchecked_into();
+                        let remaining = element.scroll_height()
+                            - element.scroll_top()
+                            - element.client_height();
+
+                        if remaining < SearchPage::SCROLL_THRESHOLD && has_next_page.get() {
+                            has_next_page.set(false);
+                            load_page(page.get() + 1);
+                        }
+                    })
+                    .children(
+                        div()
+                            .class(tw!(Display::Flex, Gap::_2))
+                            .children(Button::icon(Icon::new(IconType::Filter), move |_| {
+                                sidebar_open.set(!sidebar_open.get())
+                            }))
+                            .children(
+                                input()
+                                    .class(move || {
+                                        format!(
+                                            "{} {}",
+                                            tw!(
+                                                Width::Full,
+                                                Padding::Px3,
+                                                Padding::Py1_5,
+                                                BorderRadius::Lg
+                                            ),
+                                            theme.get().control()
+                                        )
+                                    })
+                                    .r#type("search")
+                                    .placeholder("Search series")
+                                    .bind(bind::value, query),
+                            ),
+                    )
+                    .children(move || -> View {
+                        let series = results.get_clone();
+
+                        match series.is_empty() {
+                            true => p()
+                                .class(tw!(TextColor::Gray500))
+                                .children(format!("No series found for \"{}\".", query.get_clone()))
+                                .into(),
+                            false => div()
+                                .class(tw!(Display::Grid, GridTemplateColumns::_4, Gap::_6))
+                                .children(
+                                    series
+                                        .into_iter()
+                                        .map(|series| series.into_card().into())
+                                        .collect::<Vec<View>>(),
+                                )
+                                // Stands for the next page until it's scrolled into view
+                                .when(has_next_page.get(), |this| {
+                                    this.children(
+                                        (0..SearchPage::COLUMNS)
+                                            .map(|_| Skeleton::series_poster())
+                                            .collect::<Vec<View>>(),
+                                    )
+                                })
+                                .into(),
+                        }
+                    }),
+            )
+            .into()
+    }
+}
@@ -1,23 +1,31 @@
 use rustwind::{
-    backgrounds::BackgroundColor,
     borders::BorderRadius,
-    flexbox_grid::{FlexDirection, Gap},
-    layout::{Display, ObjectFit},
+    flexbox_grid::{FlexDirection, Gap, GridTemplateColumns},
+    layout::{AspectRatio, Display, ObjectFit},
     sizing::{Height, Width},
-    typography::{FontSize, FontWeight, LineClamp, TextOverflow},
+    svg::Fill,
+    typography::{FontSize, FontWeight, TextColor, TextOverflow},
 };
 use sycamore::{
-    prelude::HtmlImgAttributes,
+    prelude::create_signal,
     web::{
-        tags::{div, h1, header, img, li, p},
+        tags::{div, h1, h3, header, iframe, p},
         GlobalProps, HtmlGlobalAttributes, View,
     },
 };
+use sycamore_router::navigate;
+use web_sys::Url;
 
 use crate::{
-    components::{Button, Icon, IconType, IntoCard, List, ListHeader},
+    components::{
+        AnimatedIcon, Button, ButtonVariant, DropdownItem, EpisodesList, Icon, IconState, IconType,
+        Image, IntoCard, Modal, RichText, SplitButton, Tabs, VideoPlayer,
+    },
+    hooks::use_app_state,
+    routes::AppRoutes,
+    storage::{Bookmark, Library, WatchHistory},
     tw,
-    types::{Episode, Series},
+    types::{self, Episode, Series, Video},
     utils::ViewBuilder,
 };
 
@@ -25,20 +33,204 @@ use super::SplitLayout;
 
 pub struct SeriesPage;
 
+impl SeriesPage {
+    const RELATED_SERIES: usize = 6;
+    const SAMPLE_EPISODES: u16 = 12;
+
+    fn watch_button(series: &Series, extension_id: &str) -> View {
+        let series_id = series.id.clone();
+        let watch = {
+            let series_id = series_id.clone();
+            move || navigate(&AppRoutes::watch_episode(&series_id, 1))
+        };
+        let mut items = vec![DropdownItem::new("From episode 1", watch.clone())];
+        if let Some(entry) = WatchHistory::latest(extension_id, &series.id) {
+            let number = entry.episode_number;
+            items.push(DropdownItem::new(
+                format!("Continue from episode {number}"),
+                move || navigate(&AppRoutes::watch_episode(&series_id, number)),
+            ));
+        }
+
+        SplitButton::new(
+            Button::icon_label(Icon::new(IconType::Play), "Watch now", move |_| watch())
+                .variant(ButtonVariant::Primary),
+            items,
+        )
+        .into()
+    }
+
+    fn bookmark_button(series: &Series, extension_id: String, episode_count: u16) -> View {
+        let bookmarked = create_signal(Library::is_bookmarked(&extension_id, &series.id));
+        let finished_episodes = WatchHistory::finished_episodes()
+            .remove(&(extension_id.clone(), series.id.clone()))
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let bookmark = Bookmark {
+            series_id: series.id.clone(),
+            extension_id,
+            title: series.title.clone(),
+            alternative_titles: series.alternative_titles.clone(),
+            genres: series.genres.clone(),
+            poster_url: series.poster_url.clone(),
+            collections: Vec::new(),
+            updated_at: 0.0,
+            episode_count: Some(episode_count),
+            finished_episodes,
+        };
+
+        let label = move || match bookmarked.get() {
+            true => "Bookmarked",
+            false => "Bookmark",
+        };
+
+        Button::icon_label(
+            AnimatedIcon::new(IconState::bookmark(*bookmarked)).active_fill(Fill::Red500),
+            label,
+            move |_| {
+                bookmarked.set(Library::toggle(Bookmark {
+                    updated_at: js_sys::Date::now(),
+                    ..bookmark.clone()
+                }))
+            },
+        )
+        .variant(ButtonVariant::Secondary)
+        .into()
+    }
+
+    /// Returns the id of a YouTube video URL, e.g. `https://youtu.be/<id>`.
+    ///
+    /// Ids are 11 characters of `[A-Za-z0-9_-]`, anything else isn't embedded.
+    fn youtube_id(url: &str) -> Option<String> {
+        let url = Url::new(url).ok()?;
+        let id = match url.hostname().trim_start_matches("www.") {
+            "youtu.be" => url.pathname().get(1..).map(str::to_owned),
+            "youtube.com" | "m.youtube.com" | "youtube-nocookie.com" => {
+                match url.pathname().as_str() {
+                    "/watch" => url.search_params().get("v"),
+                    path => path.strip_prefix("/embed/").map(str::to_owned),
+                }
+            }
+            _ => None,
+        };
+        id.filter(|id| {
+            id.len() == 11
+                && id
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-'))
+        })
+    }
+
+    /// Button opening the trailer in a dialog, embedded if it's on YouTube.
+    fn trailer(url: String) -> View {
+        let open = create_signal(false);
+
+        // Only mounted while open, so that closing the dialog stops the trailer
+        let player = move || -> View {
+            if !open.get() {
+                return View::default();
+            }
+            match SeriesPage::youtube_id(&url) {
+                Some(id) => iframe()
+                    .class(tw!(Width::Full, AspectRatio::Video))
+                    .attr(
+                        "src",
+                        format!("https://www.youtube-nocookie.com/embed/{id}?autoplay=1"),
+                    )
+                    .attr("allow", "autoplay; fullscreen; picture-in-picture")
+                    .attr("allowfullscreen", "true")
+                    .into(),
+                None => VideoPlayer::new(vec![Video {
+                    url: url.clone(),
+                    ..Video::default()
+                }])
+                .untracked()
+                .into(),
+            }
+        };
+
+        (
+            Button::icon_label(Icon::new(IconType::Play), "Watch trailer", move |_| {
+                open.set(true)
+            })
+            .variant(ButtonVariant::Secondary),
+            Modal::new(open, player)
+                .title("Trailer")
+                .width(Width::_1over2),
+        )
+            .into()
+    }
+
+    fn details(series: Series) -> View {
+        let detail = |label: &'static str, value: View| {
+            div()
+                .class(tw!(Display::Flex, FlexDirection::Col, Gap::_1))
+                .children(h3().class(tw!(FontWeight::Semibold)).children(label))
+                .children(value)
+        };
+
+        div()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_4))
+            .when_some(series.r#type, |this, r#type| {
+                this.children(detail(
+                    "Type",
+                    p().class(tw!(TextColor::Gray500)).children(r#type).into(),
+                ))
+            })
+            .when_some(series.synopsis, |this, synopsis| {
+                this.children(detail(
+                    "Synopsis",
+                    RichText::new(&synopsis)
+                        .class(tw!(TextColor::Gray500))
+                        .into(),
+                ))
+            })
+            .into()
+    }
+
+    // TODO: ask the extension for related series once they can be loaded
+    fn related() -> View {
+        let cards = types::SeriesPage::default()
+            .series
+            .into_iter()
+            .take(Self::RELATED_SERIES)
+            .map(|series| series.into_card().into())
+            .collect::<Vec<View>>();
+
+        div()
+            .class(tw!(Display::Grid, GridTemplateColumns::_3, Gap::_6))
+            .children(cards)
+            .into()
+    }
+}
+
 impl From<SeriesPage> for View {
     fn from(_: SeriesPage) -> Self {
         let series = Series::default();
+        let extension_id = use_app_state().extension_id.get_clone();
+
+        let episodes = (1..=SeriesPage::SAMPLE_EPISODES)
+            .map(|number| Episode {
+                series_id: series.id.clone(),
+                progress: WatchHistory::find(&extension_id, &series.id, &number.to_string())
+                    .map(|entry| entry.progress()),
+                ..Episode::sample(number)
+            })
+            .collect();
+        let watch_button = SeriesPage::watch_button(&series, &extension_id);
+        let bookmark_button =
+            SeriesPage::bookmark_button(&series, extension_id, SeriesPage::SAMPLE_EPISODES);
+        let series_details = series.clone();
+        let trailer_url = series.trailer_url.clone();
 
         SplitLayout::new_default(
-            img()
-                .class(tw!(
-                    Width::Full,
-                    Height::Full,
-                    BorderRadius::Xl,
-                    ObjectFit::Cover
-                ))
-                .src(series.poster_url)
-                .alt(series.title.clone()),
+            Image::new(series.poster_url, series.title.clone()).class(tw!(
+                Width::Full,
+                Height::Full,
+                BorderRadius::Xl,
+                ObjectFit::Cover
+            )),
             (
                 header()
                     .class(tw!(Display::Flex, FlexDirection::Col, Gap::_4))
@@ -53,35 +245,25 @@ impl From<SeriesPage> for View {
                     .children(
                         div()
                             .class(tw!(Display::Flex, Gap::_4))
-                            .children(
-                                Button::icon_label(
-                                    Icon::new(IconType::Play),
-                                    "Watch now",
-                                    |_| todo!(),
-                                )
-                                .color(BackgroundColor::Red300),
-                            )
+                            .children(watch_button)
+                            .children(bookmark_button)
+                            .when_some(trailer_url, |this, url| {
+                                this.children(SeriesPage::trailer(url))
+                            })
                             .children(
                                 Button::icon_label(
                                     Icon::new(IconType::Share),
                                     "Share the series",
                                     |_| todo!(),
                                 )
-                                .color(BackgroundColor::Red300),
+                                .variant(ButtonVariant::Primary),
                             ),
-                    )
-                    .when_some(series.synopsis, |this, synopsis| {
-                        this.children(p().class(tw!(LineClamp::_5)).children(synopsis))
-                    }),
-                List::new(
-                    (1..13)
-                        .map(|_| li().children(Episode::default().into_card()).into())
-                        .collect::<Vec<_>>(),
-                )
-                .header(
-                    ListHeader::new("Episodes")
-                        .end_slot(Button::icon(Icon::new(IconType::Sort), |_| todo!())),
-                ),
+                    ),
+                Tabs::new(create_signal(0))
+                    .query_param("tab")
+                    .tab("Episodes", EpisodesList::new(episodes))
+                    .tab("Details", SeriesPage::details(series_details))
+                    .tab("Related", SeriesPage::related()),
             ),
         )
         .into()
@@ -0,0 +1,227 @@
+use rustwind::{
+    backgrounds::BackgroundColor,
+    borders::BorderRadius,
+    flexbox_grid::{AlignItems, FlexDirection, FlexWrap, Gap, JustifyContent},
+    layout::Display,
+    spacing::Padding,
+    typography::{FontSize, FontWeight, TextColor},
+};
+use sycamore::{
+    prelude::{create_memo, Signal},
+    web::{
+        events::click,
+        tags::{button, div, h3, p, section},
+        GlobalProps, HtmlGlobalAttributes, View,
+    },
+};
+
+use crate::{
+    components::{ListHeader, Select},
+    hooks::{use_settings, use_theme},
+    storage::{AppSettings, FeedbackMode, Font, ImageCacheSize, StartPage, Theme},
+    tw,
+};
+
+pub struct SettingsPage;
+
+impl SettingsPage {
+    const RESOLUTIONS: [Option<u16>; 4] = [None, Some(1080), Some(720), Some(480)];
+
+    fn setting(title: &'static str, description: &'static str, control: View) -> View {
+        div()
+            .class(tw!(
+                Display::Flex,
+                AlignItems::Center,
+                JustifyContent::Between,
+                Gap::_8
+            ))
+            .children(
+                div()
+                    .class(tw!(Display::Flex, FlexDirection::Col, Gap::_1))
+                    .children(h3().class(tw!(FontWeight::Semibold)).children(title))
+                    .children(
+                        p().class(tw!(TextColor::Gray500, FontSize::Sm))
+                            .children(description),
+                    ),
+            )
+            .children(control)
+            .into()
+    }
+
+    /// Chips for each `(label, value)` option, highlighting the one returned by `get`.
+    fn choices<T: Copy + PartialEq + 'static>(
+        settings: Signal<AppSettings>,
+        options: Vec<(String, T)>,
+        get: fn(&AppSettings) -> T,
+        set: fn(&mut AppSettings, T),
+    ) -> View {
+        let theme = use_theme();
+        let chips = options
+            .into_iter()
+            .map(|(label, value)| {
+                button()
+                    .class(move || {
+                        let base =
+                            tw!(Padding::Px3, Padding::Py1, BorderRadius::Full, FontSize::Sm);
+                        let color = match settings.with(get) == value {
+                            true => tw!(BackgroundColor::Red300, TextColor::Black),
+                            false => theme.get().control(),
+                        };
+                        format!("{base} {color}")
+                    })
+                    .children(label)
+                    .on(click, move |_| {
+                        settings.update(|settings| set(settings, value))
+                    })
+                    .into()
+            })
+            .collect::<Vec<View>>();
+
+        div()
+            .class(tw!(Display::Flex, FlexWrap::Wrap, Gap::_2))
+            .children(chips)
+            .into()
+    }
+
+    fn toggle(
+        settings: Signal<AppSettings>,
+        get: fn(&AppSettings) -> bool,
+        set: fn(&mut AppSettings, bool),
+    ) -> View {
+        Self::choices(
+            settings,
+            vec![("On".to_owned(), true), ("Off".to_owned(), false)],
+            get,
+            set,
+        )
+    }
+}
+
+impl From<SettingsPage> for View {
+    fn from(_: SettingsPage) -> Self {
+        let settings = use_settings();
+
+        let themes = [
+            ("System", Theme::System),
+            ("Light", Theme::Light),
+            ("Dark", Theme::Dark),
+        ]
+        .map(|(label, theme)| (label.to_owned(), theme))
+        .to_vec();
+
+        let fonts = [
+            ("System", Font::System),
+            ("Serif", Font::Serif),
+            ("OpenDyslexic", Font::Dyslexic),
+        ]
+        .map(|(label, font)| (label.to_owned(), font))
+        .to_vec();
+
+        let feedback_modes = [
+            ("Sound", FeedbackMode::Sound),
+            ("Visual", FeedbackMode::Visual),
+            ("Off", FeedbackMode::Off),
+        ]
+        .map(|(label, mode)| (label.to_owned(), mode))
+        .to_vec();
+
+        let start_pages = [
+            ("Home", StartPage::Home),
+            ("Library", StartPage::Library),
+            ("Continue watching", StartPage::ContinueWatching),
+        ]
+        .map(|(label, page)| (label.to_owned(), page))
+        .to_vec();
+
+        let image_cache_sizes = [
+            ImageCacheSize::Small,
+            ImageCacheSize::Medium,
+            ImageCacheSize::Large,
+        ]
+        .map(|size| (format!("{} images", size.max_entries()), size))
+        .to_vec();
+
+        let resolutions = SettingsPage::RESOLUTIONS
+            .map(|resolution| match resolution {
+                Some(height) => (format!("{height}p"), resolution),
+                None => ("Auto".to_owned(), resolution),
+            })
+            .to_vec();
+
+        section()
+            .class(tw!(Display::Flex, FlexDirection::Col, Gap::_6))
+            .children(ListHeader::new("Settings").sticky(false))
+            .children(SettingsPage::setting(
+                "Theme",
+                "Appearance of the app.",
+                SettingsPage::choices(
+                    settings,
+                    themes,
+                    |settings| settings.theme,
+                    |settings, theme| settings.theme = theme,
+                ),
+            ))
+            .children(SettingsPage::setting(
+                "Font",
+                "Font of the interface. OpenDyslexic is easier to read for people with dyslexia.",
+                SettingsPage::choices(
+                    settings,
+                    fonts,
+                    |settings| settings.font,
+                    |settings, font| settings.font = font,
+                ),
+            ))
+            .children(SettingsPage::setting(
+                "Start page",
+                "Page shown when Nero opens. Continue watching resumes the last watched episode.",
+                SettingsPage::choices(
+                    settings,
+                    start_pages,
+                    |settings| settings.start_page,
+                    |settings, page| settings.start_page = page,
+                ),
+            ))
+            .children(SettingsPage::setting(
+                "Feedback",
+                "How notifications and finished episodes are signaled. Sounds are captioned.",
+                SettingsPage::choices(
+                    settings,
+                    feedback_modes,
+                    |settings| settings.feedback,
+                    |settings, mode| settings.feedback = mode,
+                ),
+            ))
+            .children(SettingsPage::setting(
+                "Default quality",
+                "Resolution picked when an episode starts, if available.",
+                Select::new(
+                    resolutions,
+                    create_memo(move || settings.with(|settings| settings.player.resolution)),
+                    move |resolution| {
+                        settings.update(|settings| settings.player.resolution = resolution)
+                    },
+                )
+                .into(),
+            ))
+            .children(SettingsPage::setting(
+                "Autoplay",
+                "Start the next episode when the current one ends.",
+                SettingsPage::toggle(
+                    settings,
+                    |settings| settings.player.auto_play,
+                    |settings, enabled| settings.player.auto_play = enabled,
+                ),
+            ))
+            .children(SettingsPage::setting(
+                "Image cache",
+                "Posters and thumbnails kept for faster loading.",
+                SettingsPage::choices(
+                    settings,
+                    image_cache_sizes,
+                    |settings| settings.image_cache,
+                    |settings, size| settings.image_cache = size,
+                ),
+            ))
+            .into()
+    }
+}
@@ -1,22 +1,24 @@
 use rustwind::{
     flexbox_grid::{FlexDirection, Gap},
-    layout::{AspectRatio, Display},
-    sizing::Width,
+    layout::Display,
     spacing::SpaceBetween,
     typography::{FontSize, FontWeight, LineClamp},
 };
 use sycamore::{
-    prelude::HtmlVideoAttributes,
+    prelude::{create_memo, create_signal, on_cleanup, Signal},
     web::{
-        tags::{h1, li, p, section, video},
+        tags::{h1, p, section},
         GlobalProps, HtmlGlobalAttributes, View,
     },
 };
+use web_sys::{HtmlVideoElement, UrlSearchParams};
 
 use crate::{
-    components::{IntoSmallCard, List},
+    components::{DisplayMode, EpisodesList, VideoPlayer},
+    hooks::{use_app_state, NowPlaying},
+    storage::{HistoryEntry, Library, WatchHistory},
     tw,
-    types::{Episode, Video},
+    types::{Episode, Series, Video},
     utils::ViewBuilder,
 };
 
@@ -24,16 +26,121 @@ use super::SplitLayout;
 
 pub struct WatchPage;
 
+impl WatchPage {
+    /// Query parameter of the series to watch, e.g. `/watch?series=spy-x-family`.
+    pub const SERIES_PARAM: &str = "series";
+    /// Query parameter of the episode to start from, e.g. `/watch?episode=3`.
+    pub const EPISODE_PARAM: &str = "episode";
+    const SAMPLE_EPISODES: u16 = 12;
+
+    fn query_param(name: &str) -> Option<String> {
+        web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .and_then(|search| UrlSearchParams::new_with_str(&search).ok())
+            .and_then(|params| params.get(name))
+    }
+
+    /// Series in the query, whose title and poster come from the history since
+    /// there is no extension to fetch it from.
+    fn initial_series(extension_id: &str) -> Series {
+        let sample = Series::default();
+        let Some(id) = Self::query_param(Self::SERIES_PARAM) else {
+            return sample;
+        };
+
+        match WatchHistory::latest(extension_id, &id) {
+            Some(entry) => Series {
+                id,
+                title: entry.series_title,
+                poster_url: entry.poster_url,
+                ..sample
+            },
+            None => Series { id, ..sample },
+        }
+    }
+
+    fn initial_episode() -> u16 {
+        Self::query_param(Self::EPISODE_PARAM)
+            .and_then(|number| number.parse().ok())
+            .filter(|number| (1..=Self::SAMPLE_EPISODES).contains(number))
+            .unwrap_or(1)
+    }
+
+    fn player(
+        series: Series,
+        episode: Episode,
+        current: Signal<u16>,
+        display_mode: Signal<DisplayMode>,
+    ) -> View {
+        let next = episode.number + 1;
+        let app = use_app_state();
+        let now_playing = app.now_playing;
+        let extension_id = app.extension_id.get_clone();
+        let start_at = WatchHistory::find(&extension_id, &series.id, &episode.id)
+            .map(|entry| entry.resume_position())
+            .unwrap_or_default();
+        let subtitles_key = format!("{extension_id}/{}/{}", series.id, episode.id);
+
+        let record = move |video: HtmlVideoElement| {
+            let duration = video.duration();
+
+            let entry = HistoryEntry {
+                extension_id: extension_id.clone(),
+                series_id: series.id.clone(),
+                series_title: series.title.clone(),
+                poster_url: series.poster_url.clone(),
+                episode_id: episode.id.clone(),
+                episode_number: episode.number,
+                position: video.current_time(),
+                duration: duration.is_finite().then_some(duration),
+                watched_at: js_sys::Date::now(),
+            };
+            now_playing.set(Some(NowPlaying {
+                series_id: entry.series_id.clone(),
+                episode_id: entry.episode_id.clone(),
+                progress: entry.progress(),
+            }));
+            if entry.progress() >= HistoryEntry::FINISHED_THRESHOLD {
+                Library::mark_finished(&extension_id, &entry.series_id, &entry.episode_id);
+            }
+            WatchHistory::record(entry);
+        };
+
+        VideoPlayer::new(Video::samples())
+            .display_mode(display_mode)
+            .start_at(start_at)
+            .subtitles_key(subtitles_key)
+            .when(next <= Self::SAMPLE_EPISODES, |player| {
+                player.on_next(move || current.set(next))
+            })
+            .on_pause(record.clone())
+            .on_progress(record)
+            .into()
+    }
+}
+
 impl From<WatchPage> for View {
     fn from(_: WatchPage) -> Self {
-        let sample_video = Video::default();
+        let extension_id = use_app_state().extension_id.get_clone();
+        let series = WatchPage::initial_series(&extension_id);
+        let series_id = series.id.clone();
+        let current = create_signal(WatchPage::initial_episode());
+        let display_mode = create_signal(DisplayMode::default());
+        let fullscreen = create_memo(move || display_mode.get() == DisplayMode::Fullscreen);
+
+        let now_playing = use_app_state().now_playing;
+        on_cleanup(move || now_playing.set(None));
 
         SplitLayout::new_watch(
             (
-                video()
-                    .class(tw!(Width::Full, AspectRatio::Video))
-                    .controls(true)
-                    .src(sample_video.url),
+                move || {
+                    WatchPage::player(
+                        series.clone(),
+                        Episode::sample(current.get()),
+                        current,
+                        display_mode,
+                    )
+                },
                 section()
                     .class(tw!(Display::Flex, FlexDirection::Col, Gap::_2))
                     .children(
@@ -49,12 +156,24 @@ impl From<WatchPage> for View {
                         this.children(p().class(tw!(LineClamp::_3)).children(synopsis))
                     }),
             ),
-            List::new(
-                (1..13)
-                    .map(|_| li().children(Episode::default().into_small_card()).into())
-                    .collect::<Vec<_>>(),
-            ),
+            EpisodesList::new(
+                (1..=WatchPage::SAMPLE_EPISODES)
+                    .map(|number| Episode {
+                        series_id: series_id.clone(),
+                        progress: WatchHistory::find(
+                            &extension_id,
+                            &series_id,
+                            &number.to_string(),
+                        )
+                        .map(|entry| entry.progress()),
+                        ..Episode::sample(number)
+                    })
+                    .collect(),
+            )
+            .small(true)
+            .on_select(move |number| current.set(number)),
         )
+        .collapsed(fullscreen)
         .into()
     }
 }
@@ -0,0 +1,275 @@
+//! Sanitizer for the HTML some sources return in synopses and descriptions.
+//!
+//! Only bold, italic, paragraphs, line breaks and external links are kept;
+//! any other tag is dropped while keeping its text, except scripts and styles
+//! which are dropped entirely.
+
+/// Formatting kept from the source HTML.
+#[derive(Clone, PartialEq)]
+pub enum RichTag {
+    Bold,
+    Italic,
+    Paragraph,
+    /// Link to an `http` or `https` URL.
+    Link(String),
+}
+
+impl RichTag {
+    fn parse(name: &str, attributes: &str) -> Option<Self> {
+        match name {
+            "b" | "strong" => Some(Self::Bold),
+            "i" | "em" => Some(Self::Italic),
+            "p" => Some(Self::Paragraph),
+            "a" => attribute(attributes, "href")
+                .filter(|href| href.starts_with("https://") || href.starts_with("http://"))
+                .map(Self::Link),
+            _ => None,
+        }
+    }
+}
+
+pub enum RichNode {
+    Text(String),
+    LineBreak,
+    Element(RichTag, Vec<RichNode>),
+}
+
+/// Elements whose content is never shown.
+const SKIPPED: [&str; 2] = ["script", "style"];
+
+/// Parses `html` into the nodes that are safe to render.
+///
+/// Unclosed tags end with their parent, and stray closing tags are ignored.
+pub fn parse(html: &str) -> Vec<RichNode> {
+    // Open elements with their tag name, the root being the first one
+    let mut stack = vec![(String::new(), RichTag::Paragraph, Vec::new())];
+    let mut skipping = None::<String>;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let (text, tag) = rest.split_at(rest.find('<').unwrap_or(rest.len()));
+        if skipping.is_none() && !text.is_empty() {
+            push(&mut stack, RichNode::Text(decode(text)));
+        }
+        if tag.is_empty() {
+            break;
+        }
+
+        if let Some(comment) = tag.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, rest)| rest);
+            continue;
+        }
+        let is_tag = tag[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/');
+        let Some((inner, after)) = tag[1..].split_once('>').filter(|_| is_tag) else {
+            // Not a tag, e.g. a lone `<` in the text
+            if skipping.is_none() {
+                push(&mut stack, RichNode::Text("<".to_owned()));
+            }
+            rest = &tag[1..];
+            continue;
+        };
+        rest = after;
+
+        let closing = inner.starts_with('/');
+        let inner = inner.trim_start_matches('/').trim_end_matches('/').trim();
+        let (name, attributes) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+        let name = name.to_ascii_lowercase();
+
+        if let Some(skipped) = &skipping {
+            if closing && name == *skipped {
+                skipping = None;
+            }
+            continue;
+        }
+
+        match closing {
+            _ if name == "br" => push(&mut stack, RichNode::LineBreak),
+            false if SKIPPED.contains(&name.as_str()) => skipping = Some(name),
+            false => {
+                if let Some(tag) = RichTag::parse(&name, attributes) {
+                    stack.push((name, tag, Vec::new()));
+                }
+            }
+            true => {
+                if let Some(index) = stack.iter().skip(1).rposition(|(open, ..)| *open == name) {
+                    close(&mut stack, index + 1);
+                }
+            }
+        }
+    }
+
+    close(&mut stack, 1);
+    stack.pop().map(|(.., nodes)| nodes).unwrap_or_default()
+}
+
+fn push(stack: &mut [(String, RichTag, Vec<RichNode>)], node: RichNode) {
+    if let Some((.., nodes)) = stack.last_mut() {
+        nodes.push(node);
+    }
+}
+
+/// Closes the open elements from `depth` onwards, moving them into their parents.
+fn close(stack: &mut Vec<(String, RichTag, Vec<RichNode>)>, depth: usize) {
+    while stack.len() > depth {
+        if let Some((_, tag, nodes)) = stack.pop() {
+            push(stack, RichNode::Element(tag, nodes));
+        }
+    }
+}
+
+/// Returns the value of the attribute `name`, quoted or not.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let lowercase = attributes.to_ascii_lowercase();
+    let pattern = format!("{name}=");
+    let (start, _) = lowercase
+        .match_indices(&pattern)
+        .find(|(index, _)| *index == 0 || lowercase[..*index].ends_with(char::is_whitespace))?;
+
+    let value = &attributes[start + pattern.len()..];
+    let value = match value.chars().next()? {
+        quote @ ('"' | '\'') => value[1..].split(quote).next()?,
+        _ => value.split_whitespace().next()?,
+    };
+    Some(decode(value))
+}
+
+/// Decodes the character references of `text`, e.g. `&amp;` or `&#39;`.
+fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let reference = rest[1..]
+            .split_once(';')
+            .filter(|(name, _)| name.len() <= 8)
+            .and_then(|(name, after)| Some((character(name)?, after)));
+        match reference {
+            Some((character, after)) => {
+                decoded.push(character);
+                rest = after;
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn character(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `nodes` back as HTML, to compare parse results as strings.
+    fn render(nodes: &[RichNode]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                RichNode::Text(text) => text.clone(),
+                RichNode::LineBreak => "<br>".to_owned(),
+                RichNode::Element(tag, children) => {
+                    let children = render(children);
+                    match tag {
+                        RichTag::Bold => format!("<b>{children}</b>"),
+                        RichTag::Italic => format!("<i>{children}</i>"),
+                        RichTag::Paragraph => format!("<p>{children}</p>"),
+                        RichTag::Link(href) => format!("<a href=\"{href}\">{children}</a>"),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn parsed(html: &str) -> String {
+        render(&parse(html))
+    }
+
+    #[test]
+    fn decodes_character_references() {
+        assert_eq!(parsed("a &lt;b&gt; &amp; &#39;c&#x27;"), "a <b> & 'c'");
+        assert_eq!(parsed("fish &chips; &unknown;"), "fish &chips; &unknown;");
+        assert_eq!(parsed("1 < 2"), "1 < 2");
+    }
+
+    #[test]
+    fn decoded_markup_stays_text() {
+        let nodes = parse("&lt;script&gt;alert(1)&lt;/script&gt;");
+        assert!(
+            matches!(nodes.as_slice(), [RichNode::Text(text)] if text == "<script>alert(1)</script>")
+        );
+    }
+
+    #[test]
+    fn drops_unknown_tags_and_skipped_content() {
+        assert_eq!(parsed("<span class=\"x\">kept</span>"), "kept");
+        assert_eq!(parsed("<img src=x onerror=alert(1)>after"), "after");
+        assert_eq!(
+            parsed("a<script>alert(1)</script>b<style>p {}</style>c"),
+            "abc"
+        );
+        assert_eq!(parsed("a<!-- hidden -->b"), "ab");
+    }
+
+    #[test]
+    fn keeps_nested_formatting() {
+        assert_eq!(
+            parsed("<p><b>bold <i>both</i></b> plain<br/></p>"),
+            "<p><b>bold <i>both</i></b> plain<br></p>"
+        );
+        assert_eq!(parsed("<STRONG><Em>x</eM></strong>"), "<b><i>x</i></b>");
+    }
+
+    #[test]
+    fn closes_unclosed_tags_with_their_parent() {
+        assert_eq!(parsed("<p><b>open</p>after"), "<p><b>open</b></p>after");
+        assert_eq!(parsed("<i>never closed"), "<i>never closed</i>");
+        assert_eq!(parsed("stray</b> close"), "stray close");
+    }
+
+    #[test]
+    fn keeps_http_links() {
+        assert_eq!(
+            parsed("<a href=\"https://example.com/?a=1&amp;b=2\">link</a>"),
+            "<a href=\"https://example.com/?a=1&b=2\">link</a>"
+        );
+        assert_eq!(
+            parsed("<a title='x' HREF=http://example.com>link</a>"),
+            "<a href=\"http://example.com\">link</a>"
+        );
+    }
+
+    #[test]
+    fn drops_links_to_other_schemes() {
+        assert_eq!(parsed("<a href=\"javascript:alert(1)\">x</a>"), "x");
+        assert_eq!(parsed("<a href=\"JavaScript:alert(1)\">x</a>"), "x");
+        assert_eq!(parsed("<a href=\"&#106;avascript:alert(1)\">x</a>"), "x");
+        assert_eq!(parsed("<a href=\" javascript:alert(1)\">x</a>"), "x");
+        assert_eq!(parsed("<a href=\"data:text/html,x\">x</a>"), "x");
+        assert_eq!(parsed("<a>no href</a>"), "no href");
+    }
+}
@@ -0,0 +1,69 @@
+use sycamore::web::View;
+use sycamore_router::Route;
+
+use crate::pages::{
+    ExtensionsPage, HistoryPage, HomePage, LibraryPage, NotFoundPage, SearchPage, SeriesPage,
+    SettingsPage, WatchPage,
+};
+
+#[derive(Route, Clone, Copy, PartialEq)]
+pub enum AppRoutes {
+    #[to("/")]
+    Home,
+    #[to("/search")]
+    Search,
+    #[to("/library")]
+    Library,
+    #[to("/history")]
+    History,
+    #[to("/extensions")]
+    Extensions,
+    #[to("/settings")]
+    Settings,
+    #[to("/series")]
+    Series,
+    #[to("/watch")]
+    Watch,
+    #[not_found]
+    NotFound,
+}
+
+impl AppRoutes {
+    pub fn path(self) -> &'static str {
+        match self {
+            AppRoutes::Home | AppRoutes::NotFound => "/",
+            AppRoutes::Search => "/search",
+            AppRoutes::Library => "/library",
+            AppRoutes::History => "/history",
+            AppRoutes::Extensions => "/extensions",
+            AppRoutes::Settings => "/settings",
+            AppRoutes::Series => "/series",
+            AppRoutes::Watch => "/watch",
+        }
+    }
+
+    /// Path of the watch page of the series `series_id`, starting at the episode `number`.
+    pub fn watch_episode(series_id: &str, number: u16) -> String {
+        format!(
+            "{}?{}={}&{}={number}",
+            AppRoutes::Watch.path(),
+            WatchPage::SERIES_PARAM,
+            js_sys::encode_uri_component(series_id),
+            WatchPage::EPISODE_PARAM
+        )
+    }
+
+    pub fn view(self) -> View {
+        match self {
+            AppRoutes::Home => HomePage.into(),
+            AppRoutes::Search => SearchPage.into(),
+            AppRoutes::Library => LibraryPage.into(),
+            AppRoutes::Settings => SettingsPage.into(),
+            AppRoutes::Series => SeriesPage.into(),
+            AppRoutes::Watch => WatchPage.into(),
+            AppRoutes::History => HistoryPage.into(),
+            AppRoutes::Extensions => ExtensionsPage.into(),
+            AppRoutes::NotFound => NotFoundPage.into(),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Trigram index for fuzzy searches over small collections, e.g. the library.
+//!
+//! Texts are lowercased and split into words, each word padded with two spaces in
+//! front and one behind, so short words and word starts weigh more. A document
+//! matches when most trigrams of the query are found in one of its texts, which
+//! tolerates typos and missing letters.
+
+use std::collections::{HashMap, HashSet};
+
+/// Fraction of the query trigrams a text must contain to match.
+const MIN_SIMILARITY: f64 = 0.5;
+
+type Trigram = [char; 3];
+
+/// Returns the words of `text`, lowercased and without punctuation.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+fn trigrams(text: &str) -> HashSet<Trigram> {
+    let mut trigrams = HashSet::new();
+    for word in words(text) {
+        let padded = ["  ", &word, " "].concat().chars().collect::<Vec<_>>();
+        trigrams.extend(
+            padded
+                .windows(3)
+                .map(|window| [window[0], window[1], window[2]]),
+        );
+    }
+    trigrams
+}
+
+/// Texts of each document, e.g. titles and genres, indexed by their trigrams.
+pub struct SearchIndex {
+    /// Documents and texts containing each trigram, as `(document, text)` indices.
+    postings: HashMap<Trigram, Vec<(usize, usize)>>,
+    /// Normalized texts of each document, matched whole before trigrams.
+    texts: Vec<Vec<String>>,
+}
+
+impl SearchIndex {
+    pub fn new<D, T>(documents: D) -> Self
+    where
+        D: IntoIterator,
+        D::Item: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut postings = HashMap::<Trigram, Vec<(usize, usize)>>::new();
+        let mut texts = Vec::new();
+
+        for (document, document_texts) in documents.into_iter().enumerate() {
+            let mut normalized = Vec::new();
+            for (text, value) in document_texts.into_iter().enumerate() {
+                let value = value.as_ref();
+                for trigram in trigrams(value) {
+                    postings.entry(trigram).or_default().push((document, text));
+                }
+                normalized.push(words(value).collect::<Vec<_>>().join(" "));
+            }
+            texts.push(normalized);
+        }
+
+        Self { postings, texts }
+    }
+
+    /// Returns the documents matching `query`, best matches first and in index order
+    /// otherwise. Texts containing the query as is rank above fuzzy matches.
+    pub fn search(&self, query: &str) -> Vec<usize> {
+        let query_trigrams = trigrams(query);
+        if query_trigrams.is_empty() {
+            return Vec::new();
+        }
+        let normalized = words(query).collect::<Vec<_>>().join(" ");
+
+        let mut shared = HashMap::<(usize, usize), usize>::new();
+        for trigram in &query_trigrams {
+            for &posting in self.postings.get(trigram).into_iter().flatten() {
+                *shared.entry(posting).or_default() += 1;
+            }
+        }
+
+        let mut scores = HashMap::<usize, f64>::new();
+        for ((document, text), count) in shared {
+            let score = match self.texts[document][text].contains(&normalized) {
+                true => 2.0,
+                false => count as f64 / query_trigrams.len() as f64,
+            };
+            let best = scores.entry(document).or_default();
+            *best = best.max(score);
+        }
+
+        let mut matches = scores
+            .into_iter()
+            .filter(|&(_, score)| score >= MIN_SIMILARITY)
+            .collect::<Vec<_>>();
+        matches.sort_by(|(a, a_score), (b, b_score)| b_score.total_cmp(a_score).then(a.cmp(b)));
+        matches.into_iter().map(|(document, _)| document).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> SearchIndex {
+        SearchIndex::new([
+            vec!["SPY x FAMILY", "Supai Famirī", "Action", "Comedy"],
+            vec![
+                "Frieren: Beyond Journey's End",
+                "Sousou no Frieren",
+                "Fantasy",
+            ],
+            vec!["Family Guy", "Comedy"],
+        ])
+    }
+
+    #[test]
+    fn exact_matches_ignore_case_and_punctuation() {
+        assert_eq!(index().search("frieren beyond"), [1]);
+        assert_eq!(index().search("Spy-x-Family")[0], 0);
+    }
+
+    #[test]
+    fn typos_still_match() {
+        assert_eq!(index().search("freiren"), [1]);
+        assert_eq!(index().search("spy famly"), [0]);
+    }
+
+    #[test]
+    fn alternative_titles_and_genres_match() {
+        assert_eq!(index().search("sousou"), [1]);
+        assert_eq!(index().search("comedy"), [0, 2]);
+    }
+
+    #[test]
+    fn exact_matches_rank_first() {
+        assert_eq!(index().search("family guy"), [2, 0]);
+        assert_eq!(index().search("spy x family"), [0, 2]);
+    }
+
+    #[test]
+    fn unrelated_and_empty_queries_match_nothing() {
+        assert!(index().search("mecha").is_empty());
+        assert!(index().search("  !? ").is_empty());
+    }
+}
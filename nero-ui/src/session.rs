@@ -0,0 +1,29 @@
+use std::cell::Cell;
+
+thread_local! {
+    static STATS: Cell<SessionStats> = Cell::new(SessionStats::default());
+}
+
+/// Viewing statistics since the app was opened.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct SessionStats {
+    /// Seconds of video played.
+    pub watched: f64,
+    pub episodes_completed: u32,
+    /// Episodes completed since the last break reminder was dismissed.
+    pub consecutive_episodes: u32,
+}
+
+impl SessionStats {
+    pub fn get() -> Self {
+        STATS.get()
+    }
+
+    /// Applies `f` to the session statistics, returning the updated value.
+    pub fn update(f: impl FnOnce(&mut SessionStats)) -> Self {
+        let mut stats = STATS.get();
+        f(&mut stats);
+        STATS.set(stats);
+        stats
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{load, save};
+
+/// A single watched episode, as shown in the "continue watching" carousel.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// Extension the series comes from, series ids are only unique within it.
+    #[serde(default)]
+    pub extension_id: String,
+    pub series_id: String,
+    pub series_title: String,
+    pub poster_url: Option<String>,
+    pub episode_id: String,
+    pub episode_number: u16,
+    /// Playback position in seconds.
+    pub position: f64,
+    /// Duration of the episode in seconds, if it was known when saved.
+    pub duration: Option<f64>,
+    /// Milliseconds since the Unix epoch of the last update.
+    pub watched_at: f64,
+}
+
+impl HistoryEntry {
+    /// Fraction after which an episode counts as watched and restarts from the beginning.
+    pub const FINISHED_THRESHOLD: f64 = 0.95;
+
+    /// Whether the entry is about `series_id` of `extension_id`.
+    pub fn is(&self, extension_id: &str, series_id: &str) -> bool {
+        self.extension_id == extension_id && self.series_id == series_id
+    }
+
+    /// Whether the entry is about the same episode as `other`.
+    fn same_episode(&self, other: &HistoryEntry) -> bool {
+        self.is(&other.extension_id, &other.series_id) && self.episode_id == other.episode_id
+    }
+
+    /// Returns the watched fraction of the episode, between `0.0` and `1.0`.
+    pub fn progress(&self) -> f64 {
+        match self.duration {
+            Some(duration) if duration > 0.0 => (self.position / duration).clamp(0.0, 1.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the position playback should resume from, in seconds.
+    pub fn resume_position(&self) -> f64 {
+        match self.progress() >= Self::FINISHED_THRESHOLD {
+            true => 0.0,
+            false => self.position,
+        }
+    }
+}
+
+pub struct WatchHistory;
+
+impl WatchHistory {
+    const KEY: &str = "nero.history";
+    const MAX_ENTRIES: usize = 200;
+
+    /// Returns every entry, most recently watched first.
+    pub fn entries() -> Vec<HistoryEntry> {
+        load(Self::KEY).unwrap_or_default()
+    }
+
+    /// Returns the latest entry of each series, most recently watched first.
+    pub fn recent_series(limit: usize) -> Vec<HistoryEntry> {
+        let mut series = Vec::<HistoryEntry>::new();
+        for entry in Self::entries() {
+            if series.len() == limit {
+                break;
+            }
+            if !series
+                .iter()
+                .any(|e| e.is(&entry.extension_id, &entry.series_id))
+            {
+                series.push(entry);
+            }
+        }
+        series
+    }
+
+    /// Returns the latest entry of a series, if any of its episodes was watched.
+    pub fn latest(extension_id: &str, series_id: &str) -> Option<HistoryEntry> {
+        Self::entries()
+            .into_iter()
+            .find(|e| e.is(extension_id, series_id))
+    }
+
+    /// Returns the entry of a specific episode, if it was watched.
+    pub fn find(extension_id: &str, series_id: &str, episode_id: &str) -> Option<HistoryEntry> {
+        Self::entries()
+            .into_iter()
+            .find(|e| e.is(extension_id, series_id) && e.episode_id == episode_id)
+    }
+
+    /// Returns the ids of the episodes watched until the end, by extension and series.
+    pub fn finished_episodes() -> HashMap<(String, String), HashSet<String>> {
+        let mut finished = HashMap::<(String, String), HashSet<String>>::new();
+        for entry in Self::entries() {
+            if entry.progress() >= HistoryEntry::FINISHED_THRESHOLD {
+                finished
+                    .entry((entry.extension_id, entry.series_id))
+                    .or_default()
+                    .insert(entry.episode_id);
+            }
+        }
+        finished
+    }
+
+    /// Inserts or updates the entry for the same series and episode, moving it to the front.
+    pub fn record(entry: HistoryEntry) {
+        let mut entries = Self::entries();
+        entries.retain(|e| !e.same_episode(&entry));
+        entries.insert(0, entry);
+        entries.truncate(Self::MAX_ENTRIES);
+
+        save(Self::KEY, &entries);
+    }
+
+    /// Removes the entry of a specific episode, if it was watched.
+    pub fn remove(extension_id: &str, series_id: &str, episode_id: &str) {
+        let mut entries = Self::entries();
+        entries.retain(|e| !(e.is(extension_id, series_id) && e.episode_id == episode_id));
+
+        save(Self::KEY, &entries);
+    }
+
+    /// Removes every entry.
+    pub fn clear() {
+        save(Self::KEY, &Vec::<HistoryEntry>::new());
+    }
+}
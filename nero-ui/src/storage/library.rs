@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+
+use super::{load, save};
+
+/// A series saved to the library.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub series_id: String,
+    pub extension_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub alternative_titles: Vec<String>,
+    #[serde(default)]
+    pub genres: Vec<String>,
+    pub poster_url: Option<String>,
+    /// Names of the collections the series was added to.
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Milliseconds since the Unix epoch of the last update.
+    pub updated_at: f64,
+    /// Episodes the series had when it was bookmarked, if known.
+    // A "new episodes" count needs the latest count of every saved series, which
+    // nothing fetches until extensions can be queried in the background
+    #[serde(default)]
+    pub episode_count: Option<u16>,
+    /// Ids of the episodes watched until the end, kept here since the history only
+    /// holds the latest entries.
+    #[serde(default)]
+    pub finished_episodes: Vec<String>,
+}
+
+impl Bookmark {
+    /// Whether the bookmark saves `series_id` of `extension_id`. Series ids are only
+    /// unique within the extension they come from.
+    pub fn is(&self, extension_id: &str, series_id: &str) -> bool {
+        self.extension_id == extension_id && self.series_id == series_id
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct LibraryData {
+    bookmarks: Vec<Bookmark>,
+    collections: Vec<String>,
+}
+
+pub struct Library;
+
+impl Library {
+    const KEY: &str = "nero.library";
+
+    fn data() -> LibraryData {
+        load(Self::KEY).unwrap_or_default()
+    }
+
+    fn update(f: impl FnOnce(&mut LibraryData)) {
+        let mut data = Self::data();
+        f(&mut data);
+        save(Self::KEY, &data);
+    }
+
+    /// Applies `f` to the bookmark of `series_id` of `extension_id`, if saved.
+    fn update_bookmark(extension_id: &str, series_id: &str, f: impl FnOnce(&mut Bookmark)) {
+        Self::update(|data| {
+            if let Some(bookmark) = data
+                .bookmarks
+                .iter_mut()
+                .find(|bookmark| bookmark.is(extension_id, series_id))
+            {
+                f(bookmark);
+            }
+        });
+    }
+
+    /// Returns every bookmark, most recently updated first.
+    pub fn bookmarks() -> Vec<Bookmark> {
+        let mut bookmarks = Self::data().bookmarks;
+        bookmarks.sort_by(|a, b| b.updated_at.total_cmp(&a.updated_at));
+        bookmarks
+    }
+
+    /// Returns the user-defined collections, in creation order.
+    pub fn collections() -> Vec<String> {
+        Self::data().collections
+    }
+
+    pub fn is_bookmarked(extension_id: &str, series_id: &str) -> bool {
+        Self::data()
+            .bookmarks
+            .iter()
+            .any(|bookmark| bookmark.is(extension_id, series_id))
+    }
+
+    /// Adds the bookmark, or removes it if the series was already saved.
+    /// Returns whether the series is bookmarked afterwards.
+    pub fn toggle(bookmark: Bookmark) -> bool {
+        let mut bookmarked = false;
+        Self::update(|data| {
+            let count = data.bookmarks.len();
+            data.bookmarks
+                .retain(|b| !b.is(&bookmark.extension_id, &bookmark.series_id));
+
+            if data.bookmarks.len() == count {
+                data.bookmarks.push(bookmark);
+                bookmarked = true;
+            }
+        });
+        bookmarked
+    }
+
+    /// Records that an episode of a saved series was watched until the end.
+    pub fn mark_finished(extension_id: &str, series_id: &str, episode_id: &str) {
+        let mut data = Self::data();
+        let Some(bookmark) = data
+            .bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.is(extension_id, series_id))
+        else {
+            return;
+        };
+        // Called on every progress update, so unchanged bookmarks aren't saved again
+        if bookmark.finished_episodes.iter().any(|id| id == episode_id) {
+            return;
+        }
+
+        bookmark.finished_episodes.push(episode_id.to_owned());
+        save(Self::KEY, &data);
+    }
+
+    /// Creates an empty collection, unless one with the same name exists.
+    pub fn create_collection(name: &str) {
+        Self::update(|data| {
+            if !data.collections.iter().any(|c| c == name) {
+                data.collections.push(name.to_owned());
+            }
+        });
+    }
+
+    /// Deletes a collection, keeping the bookmarks it contained.
+    pub fn remove_collection(name: &str) {
+        Self::update(|data| {
+            data.collections.retain(|c| c != name);
+            for bookmark in &mut data.bookmarks {
+                bookmark.collections.retain(|c| c != name);
+            }
+        });
+    }
+
+    /// Adds the series to the collection, or removes it if it was already there.
+    pub fn toggle_collection(extension_id: &str, series_id: &str, name: &str) {
+        Self::update_bookmark(extension_id, series_id, |bookmark| {
+            match bookmark.collections.iter().position(|c| c == name) {
+                Some(index) => {
+                    bookmark.collections.remove(index);
+                }
+                None => bookmark.collections.push(name.to_owned()),
+            }
+        });
+    }
+}
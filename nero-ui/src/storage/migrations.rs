@@ -0,0 +1,377 @@
+#[cfg(test)]
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::Value;
+use web_sys::Storage;
+
+use super::{is_backup_key, local_storage};
+
+/// Version of the data written by this build, bumped with each migration.
+pub const SCHEMA_VERSION: u32 = 3;
+
+const VERSION_KEY: &str = "nero.schema_version";
+/// Prefix of every key written by the app.
+const KEY_PREFIX: &str = "nero.";
+/// Prefix of the copies made before migrating, e.g. `nero.snapshot.v1/nero.library`.
+const SNAPSHOT_PREFIX: &str = "nero.snapshot.";
+/// Largest value copied to the snapshot, bigger ones (e.g. subtitle files) would
+/// risk filling the storage in the middle of an upgrade.
+const MAX_SNAPSHOT_VALUE_SIZE: usize = 128 * 1024;
+
+/// Key-value store the migrations run on: the local storage in the app, and a
+/// `HashMap` in tests.
+trait KeyValueStore {
+    fn get(&self, key: &str) -> Option<String>;
+    /// Returns whether the value was written.
+    fn set(&mut self, key: &str, value: &str) -> bool;
+    fn remove(&mut self, key: &str);
+    fn keys(&self) -> Vec<String>;
+}
+
+impl KeyValueStore for Storage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.get_item(key).ok().flatten()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        self.set_item(key, value).is_ok()
+    }
+
+    fn remove(&mut self, key: &str) {
+        let _ = self.remove_item(key);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        (0..self.length().unwrap_or_default())
+            .filter_map(|index| self.key(index).ok().flatten())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl KeyValueStore for HashMap<String, String> {
+    fn get(&self, key: &str) -> Option<String> {
+        HashMap::get(self, key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        self.insert(key.to_owned(), value.to_owned());
+        true
+    }
+
+    fn remove(&mut self, key: &str) {
+        HashMap::remove(self, key);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        HashMap::keys(self).cloned().collect()
+    }
+}
+
+/// Upgrades the data from one version to the next, in order: the first one goes
+/// from version 0 to 1.
+const MIGRATIONS: [fn(&mut dyn KeyValueStore); SCHEMA_VERSION as usize] = [
+    // Data saved before it was versioned, fields added since then have defaults
+    |_| {},
+    move_player_preferences,
+    key_by_extension,
+];
+
+/// Moves the player preferences, first saved on their own under `nero.player`,
+/// into the app settings.
+fn move_player_preferences(store: &mut dyn KeyValueStore) {
+    const OLD_KEY: &str = "nero.player";
+    const SETTINGS_KEY: &str = "nero.settings";
+
+    let read =
+        |store: &dyn KeyValueStore, key| serde_json::from_str::<Value>(&store.get(key)?).ok();
+    let Some(player) = read(store, OLD_KEY) else {
+        return;
+    };
+
+    let mut settings = read(store, SETTINGS_KEY)
+        .filter(Value::is_object)
+        .unwrap_or_else(|| Value::Object(Default::default()));
+    settings["player"] = player;
+
+    if store.set(SETTINGS_KEY, &settings.to_string()) {
+        store.remove(OLD_KEY);
+    }
+}
+
+/// Adds the extension to the history entries and the keys of the subtitle files,
+/// first saved by series only. Only the sample extension existed then.
+fn key_by_extension(store: &mut dyn KeyValueStore) {
+    const HISTORY_KEY: &str = "nero.history";
+    const SUBTITLES_KEY: &str = "nero.subtitles";
+    const EXTENSION_ID: &str = "sample";
+
+    let read =
+        |store: &dyn KeyValueStore, key| serde_json::from_str::<Value>(&store.get(key)?).ok();
+
+    if let Some(Value::Array(mut entries)) = read(store, HISTORY_KEY) {
+        for entry in entries.iter_mut().filter_map(Value::as_object_mut) {
+            entry
+                .entry("extension_id")
+                .or_insert_with(|| EXTENSION_ID.into());
+        }
+        store.set(HISTORY_KEY, &Value::Array(entries).to_string());
+    }
+
+    if let Some(Value::Object(files)) = read(store, SUBTITLES_KEY) {
+        let files = files
+            .into_iter()
+            .map(|(key, file)| (format!("{EXTENSION_ID}/{key}"), file))
+            .collect();
+        store.set(SUBTITLES_KEY, &Value::Object(files).to_string());
+    }
+}
+
+/// Returned when the data was written by a newer version of the app, which this
+/// one can't read without losing what it doesn't know about.
+#[derive(Debug)]
+pub struct NewerSchema(pub u32);
+
+impl fmt::Display for NewerSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Your data was saved by a newer version of Nero (schema {}, this one supports up \
+             to {SCHEMA_VERSION}). Update Nero to keep using it.",
+            self.0
+        )
+    }
+}
+
+/// Keys written by the app, except the snapshots.
+fn app_keys(store: &dyn KeyValueStore) -> Vec<String> {
+    store
+        .keys()
+        .into_iter()
+        .filter(|key| key.starts_with(KEY_PREFIX) && !key.starts_with(SNAPSHOT_PREFIX))
+        .collect()
+}
+
+/// Removes the snapshot left by a previous upgrade, if any.
+fn remove_snapshot(store: &mut dyn KeyValueStore) {
+    for key in store.keys() {
+        if key.starts_with(SNAPSHOT_PREFIX) {
+            store.remove(&key);
+        }
+    }
+}
+
+/// Copies the values of the app, so the data of `version` can be recovered by hand
+/// if a migration goes wrong. Only the latest snapshot is kept, and backups and
+/// large values are left out.
+fn snapshot(store: &mut dyn KeyValueStore, version: u32) {
+    remove_snapshot(store);
+
+    for key in app_keys(store) {
+        if is_backup_key(&key) {
+            continue;
+        }
+        if let Some(value) = store
+            .get(&key)
+            .filter(|value| value.len() <= MAX_SNAPSHOT_VALUE_SIZE)
+        {
+            store.set(&format!("{SNAPSHOT_PREFIX}v{version}/{key}"), &value);
+        }
+    }
+}
+
+/// Brings the saved data up to [`SCHEMA_VERSION`], before anything reads it.
+///
+/// Fails without touching the data if it comes from a newer version.
+pub fn migrate() -> Result<(), NewerSchema> {
+    match local_storage() {
+        Some(mut storage) => migrate_store(&mut storage),
+        None => Ok(()),
+    }
+}
+
+/// Removes the snapshot taken by [`migrate`], once the app started on the
+/// migrated data.
+pub fn discard_snapshot() {
+    if let Some(mut storage) = local_storage() {
+        remove_snapshot(&mut storage);
+    }
+}
+
+fn migrate_store(store: &mut dyn KeyValueStore) -> Result<(), NewerSchema> {
+    let version = match store
+        .get(VERSION_KEY)
+        .and_then(|version| version.parse().ok())
+    {
+        Some(version) => version,
+        // Nothing to migrate on a fresh install
+        None if app_keys(store).is_empty() => SCHEMA_VERSION,
+        None => 0,
+    };
+
+    if version > SCHEMA_VERSION {
+        return Err(NewerSchema(version));
+    }
+    if version < SCHEMA_VERSION {
+        snapshot(store, version);
+    }
+
+    for (to, migration) in (1_u32..).zip(MIGRATIONS).skip(version as usize) {
+        migration(store);
+        // Saved after each step, so an interrupted upgrade resumes where it stopped
+        store.set(VERSION_KEY, &to.to_string());
+    }
+    store.set(VERSION_KEY, &SCHEMA_VERSION.to_string());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn fresh_install_is_stamped_with_the_current_version() {
+        let mut store = store(&[]);
+        assert!(migrate_store(&mut store).is_ok());
+        assert_eq!(store.get(VERSION_KEY), Some(&SCHEMA_VERSION.to_string()));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn unversioned_data_is_migrated_and_stamped() {
+        let mut store = store(&[("nero.player", r#"{"volume":0.5}"#)]);
+        assert!(migrate_store(&mut store).is_ok());
+        assert_eq!(store.get(VERSION_KEY), Some(&SCHEMA_VERSION.to_string()));
+        assert_eq!(
+            store.get("nero.settings").map(String::as_str),
+            Some(r#"{"player":{"volume":0.5}}"#)
+        );
+    }
+
+    #[test]
+    fn newer_version_is_refused_untouched() {
+        let newer = (SCHEMA_VERSION + 1).to_string();
+        let mut store = store(&[(VERSION_KEY, &newer), ("nero.settings", "{}")]);
+        let before = store.clone();
+
+        assert!(matches!(
+            migrate_store(&mut store),
+            Err(NewerSchema(version)) if version == SCHEMA_VERSION + 1
+        ));
+        assert_eq!(store, before);
+    }
+
+    #[test]
+    fn current_version_is_left_as_is() {
+        let current = SCHEMA_VERSION.to_string();
+        let mut store = store(&[(VERSION_KEY, &current), ("nero.player", "{}")]);
+        let before = store.clone();
+
+        assert!(migrate_store(&mut store).is_ok());
+        assert_eq!(store, before);
+    }
+
+    #[test]
+    fn player_preferences_move_into_the_settings() {
+        let mut store = store(&[
+            ("nero.player", r#"{"volume":0.5}"#),
+            ("nero.settings", r#"{"theme":"Dark"}"#),
+        ]);
+        move_player_preferences(&mut store);
+
+        let settings: Value = serde_json::from_str(&store["nero.settings"]).unwrap();
+        assert_eq!(settings["theme"], "Dark");
+        assert_eq!(settings["player"]["volume"], 0.5);
+        assert!(!store.contains_key("nero.player"));
+    }
+
+    #[test]
+    fn player_preferences_replace_unreadable_settings() {
+        let mut store = store(&[("nero.player", "{}"), ("nero.settings", "[1, 2]")]);
+        move_player_preferences(&mut store);
+
+        assert_eq!(store["nero.settings"], r#"{"player":{}}"#);
+    }
+
+    #[test]
+    fn missing_player_preferences_leave_the_settings() {
+        let mut store = store(&[("nero.settings", r#"{"theme":"Dark"}"#)]);
+        move_player_preferences(&mut store);
+
+        assert_eq!(store["nero.settings"], r#"{"theme":"Dark"}"#);
+    }
+
+    #[test]
+    fn history_and_subtitles_are_keyed_by_extension() {
+        let mut store = store(&[
+            (
+                "nero.history",
+                r#"[{"series_id":"a","episode_id":"1"},{"extension_id":"other","series_id":"b"}]"#,
+            ),
+            ("nero.subtitles", r#"{"a/1":{"name":"a.srt"}}"#),
+        ]);
+        key_by_extension(&mut store);
+
+        let history: Value = serde_json::from_str(&store["nero.history"]).unwrap();
+        assert_eq!(history[0]["extension_id"], "sample");
+        assert_eq!(history[0]["series_id"], "a");
+        assert_eq!(history[1]["extension_id"], "other");
+
+        let subtitles: Value = serde_json::from_str(&store["nero.subtitles"]).unwrap();
+        assert_eq!(subtitles["sample/a/1"]["name"], "a.srt");
+        assert!(subtitles.get("a/1").is_none());
+    }
+
+    #[test]
+    fn missing_history_and_subtitles_are_left_missing() {
+        let mut store = store(&[("nero.settings", "{}")]);
+        key_by_extension(&mut store);
+
+        assert_eq!(store, self::store(&[("nero.settings", "{}")]));
+    }
+
+    #[test]
+    fn snapshot_skips_backups_and_large_values() {
+        let large = "x".repeat(MAX_SNAPSHOT_VALUE_SIZE + 1);
+        let mut store = store(&[
+            ("nero.library", "{}"),
+            ("nero.library.backup", "{}"),
+            ("nero.library.backup.2", "{}"),
+            ("nero.subtitles", &large),
+            ("other.key", "{}"),
+        ]);
+        snapshot(&mut store, 1);
+
+        let mut snapshot = store
+            .keys()
+            .filter(|key| key.starts_with(SNAPSHOT_PREFIX))
+            .cloned()
+            .collect::<Vec<_>>();
+        snapshot.sort();
+        assert_eq!(snapshot, ["nero.snapshot.v1/nero.library"]);
+    }
+
+    #[test]
+    fn snapshot_replaces_the_previous_one() {
+        let mut store = store(&[
+            ("nero.snapshot.v0/nero.library", "[]"),
+            ("nero.library", "{}"),
+        ]);
+        snapshot(&mut store, 1);
+
+        assert!(!store.contains_key("nero.snapshot.v0/nero.library"));
+        assert!(store.contains_key("nero.snapshot.v1/nero.library"));
+
+        remove_snapshot(&mut store);
+        assert_eq!(store, self::store(&[("nero.library", "{}")]));
+    }
+}
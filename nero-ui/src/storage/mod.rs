@@ -0,0 +1,222 @@
+mod history;
+mod library;
+mod migrations;
+mod preferences;
+mod settings;
+mod subtitles;
+
+pub use history::*;
+pub use library::*;
+pub use migrations::*;
+pub use preferences::*;
+pub use settings::*;
+pub use subtitles::*;
+
+use std::{cell::RefCell, collections::HashSet};
+
+use serde::{de::DeserializeOwned, Serialize};
+use web_sys::Storage;
+
+/// Suffix of the keys holding the previous values of each store, the latest one
+/// under `{key}.backup` and older ones under `{key}.backup.2` and so on.
+const BACKUP_SUFFIX: &str = ".backup";
+/// Previous values kept for each store.
+const BACKUP_VERSIONS: usize = 3;
+
+/// Key of the `version`th most recent backup of `key`, starting from 1.
+fn backup_key(key: &str, version: usize) -> String {
+    match version {
+        1 => format!("{key}{BACKUP_SUFFIX}"),
+        _ => format!("{key}{BACKUP_SUFFIX}.{version}"),
+    }
+}
+
+/// Whether `key` holds a backup of another key.
+pub(super) fn is_backup_key(key: &str) -> bool {
+    key.ends_with(BACKUP_SUFFIX)
+        || key
+            .rsplit_once(&format!("{BACKUP_SUFFIX}."))
+            .is_some_and(|(_, version)| version.parse::<usize>().is_ok())
+}
+
+/// A problem with a store, reported to [`on_issue`].
+pub enum StorageIssue {
+    /// The value wasn't valid JSON and was replaced with its backup, or removed.
+    Corrupted { key: String, restored: bool },
+    /// The value is valid JSON that this version can't read. It is left untouched,
+    /// and its backup or the defaults are used instead.
+    Unreadable { key: String, restored: bool },
+    /// The value couldn't be written, e.g. because the storage is full.
+    WriteFailed { key: String },
+}
+
+impl StorageIssue {
+    /// Describes what was lost, for the error log.
+    pub fn message(&self) -> String {
+        match self {
+            StorageIssue::Corrupted {
+                key,
+                restored: true,
+            } => format!(
+                "{key} was unreadable, restored its previous version: the last change was lost"
+            ),
+            StorageIssue::Corrupted {
+                key,
+                restored: false,
+            } => format!("{key} was unreadable and had no backup, it was reset"),
+            StorageIssue::Unreadable {
+                key,
+                restored: true,
+            } => format!("{key} doesn't match this version, using its previous version"),
+            StorageIssue::Unreadable {
+                key,
+                restored: false,
+            } => format!("{key} doesn't match this version, using the defaults"),
+            StorageIssue::WriteFailed { key } => {
+                format!("Couldn't save {key}, the storage may be full")
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// Issues that happened before [`on_issue`] was called.
+    static PENDING_ISSUES: RefCell<Vec<StorageIssue>> = const { RefCell::new(Vec::new()) };
+    static ON_ISSUE: RefCell<Option<Box<dyn Fn(StorageIssue)>>> = const { RefCell::new(None) };
+    /// Keys reported as unreadable, which are read again until they are saved.
+    static UNREADABLE_KEYS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Calls `f` for each storage issue from now on, and for those that happened before.
+pub fn on_issue(f: impl Fn(StorageIssue) + 'static) {
+    PENDING_ISSUES.take().into_iter().for_each(&f);
+    ON_ISSUE.set(Some(Box::new(f)));
+}
+
+fn report(issue: StorageIssue) {
+    ON_ISSUE.with_borrow(|on_issue| match on_issue {
+        Some(on_issue) => on_issue(issue),
+        None => PENDING_ISSUES.with_borrow_mut(|pending| pending.push(issue)),
+    });
+}
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Reads the value stored under `key`, returning `None` if it is missing.
+///
+/// A value that isn't valid JSON is replaced with its most recent readable backup,
+/// or removed if there is none. Valid JSON that doesn't match `T` (e.g. written by
+/// another version) is left as is, and the backup is only used in its place. Both
+/// are reported to [`on_issue`].
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let storage = local_storage()?;
+    let raw = storage.get_item(key).ok().flatten()?;
+    let corrupted = match serde_json::from_str::<serde_json::Value>(&raw) {
+        Ok(json) => match serde_json::from_value(json) {
+            Ok(value) => return Some(value),
+            Err(_) => false,
+        },
+        Err(_) => true,
+    };
+
+    let backup = (1..=BACKUP_VERSIONS).find_map(|version| {
+        let backup = storage.get_item(&backup_key(key, version)).ok().flatten()?;
+        Some((serde_json::from_str(&backup).ok()?, backup))
+    });
+    let key = key.to_owned();
+    let restored = backup.is_some();
+
+    match corrupted {
+        true => {
+            let _ = match &backup {
+                Some((_, backup)) => storage.set_item(&key, backup),
+                None => storage.remove_item(&key),
+            };
+            report(StorageIssue::Corrupted { key, restored });
+        }
+        false => {
+            if UNREADABLE_KEYS.with_borrow_mut(|keys| keys.insert(key.clone())) {
+                report(StorageIssue::Unreadable { key, restored });
+            }
+        }
+    }
+
+    backup.map(|(value, _)| value)
+}
+
+/// Stores `value` under `key`, reporting failures (e.g. quota exceeded) to [`on_issue`].
+///
+/// The value is written first, then the previous one becomes the latest of the
+/// [`BACKUP_VERSIONS`] backups [`load`] falls back on, if it was valid JSON. Each
+/// write replaces the whole value, so it can't be left half written.
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    let (Some(storage), Ok(raw)) = (local_storage(), serde_json::to_string(value)) else {
+        return;
+    };
+    let previous = storage.get_item(key).ok().flatten();
+    if !write(&storage, key, &raw) {
+        return;
+    }
+    let Some(previous) =
+        previous.filter(|previous| serde_json::from_str::<serde_json::Value>(previous).is_ok())
+    else {
+        return;
+    };
+
+    // Best effort: a backup that can't be written is removed rather than left outdated
+    let shift = |version: usize, value: Option<String>| {
+        let key = backup_key(key, version);
+        if !value.is_some_and(|value| storage.set_item(&key, &value).is_ok()) {
+            let _ = storage.remove_item(&key);
+        }
+    };
+    for version in (2..=BACKUP_VERSIONS).rev() {
+        let older = storage
+            .get_item(&backup_key(key, version - 1))
+            .ok()
+            .flatten();
+        shift(version, older);
+    }
+    shift(1, Some(previous));
+}
+
+/// Stores `value` under `key` without a backup, for large data the user can load
+/// again, such as subtitle files.
+pub fn save_without_backup<T: Serialize>(key: &str, value: &T) {
+    if let (Some(storage), Ok(raw)) = (local_storage(), serde_json::to_string(value)) {
+        write(&storage, key, &raw);
+    }
+}
+
+/// Writes `raw` under `key`, returning whether it succeeded.
+fn write(storage: &Storage, key: &str, raw: &str) -> bool {
+    let written = storage.set_item(key, raw).is_ok();
+    if !written {
+        report(StorageIssue::WriteFailed {
+            key: key.to_owned(),
+        });
+    }
+    written
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_keys_are_versioned() {
+        assert_eq!(backup_key("nero.library", 1), "nero.library.backup");
+        assert_eq!(backup_key("nero.library", 3), "nero.library.backup.3");
+    }
+
+    #[test]
+    fn backup_keys_are_recognized() {
+        assert!(is_backup_key("nero.library.backup"));
+        assert!(is_backup_key("nero.library.backup.2"));
+        assert!(!is_backup_key("nero.library"));
+        assert!(!is_backup_key("nero.backups"));
+        assert!(!is_backup_key("nero.library.backup.old"));
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Player choices remembered across episodes.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerPreferences {
+    /// Preferred vertical resolution (e.g. `1080`), if the user picked one.
+    pub resolution: Option<u16>,
+    /// Language of the source subtitles shown by default (e.g. `en`), if any.
+    pub subtitle_language: Option<String>,
+    /// Whether the next episode starts automatically when one ends.
+    pub auto_play: bool,
+    /// Speed videos play at, `1.0` being normal.
+    pub playback_rate: f64,
+    /// Whether the session statistics are shown over the player.
+    pub session_stats: bool,
+    /// Consecutive episodes after which a break is suggested, if enabled.
+    pub break_reminder: Option<u32>,
+}
+
+impl Default for PlayerPreferences {
+    fn default() -> Self {
+        PlayerPreferences {
+            resolution: None,
+            subtitle_language: None,
+            auto_play: true,
+            playback_rate: 1.0,
+            session_stats: false,
+            break_reminder: Some(3),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use super::{load, save, PlayerPreferences};
+
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    /// Follows the theme of the operating system.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// Font family of the interface.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Font {
+    /// Sans-serif font of the operating system.
+    #[default]
+    System,
+    Serif,
+    /// OpenDyslexic, bundled with the app, easier to read for people with dyslexia.
+    Dyslexic,
+}
+
+/// How events like notifications are signaled, see `Feedback`.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum FeedbackMode {
+    /// A chime, captioned by a visual flash.
+    Sound,
+    /// A visual flash only.
+    Visual,
+    #[default]
+    Off,
+}
+
+/// Images kept in the image cache, the least recently shown ones are evicted first.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ImageCacheSize {
+    Small,
+    #[default]
+    Medium,
+    Large,
+}
+
+impl ImageCacheSize {
+    pub fn max_entries(self) -> u32 {
+        match self {
+            ImageCacheSize::Small => 100,
+            ImageCacheSize::Medium => 300,
+            ImageCacheSize::Large => 1000,
+        }
+    }
+}
+
+/// Page shown when the app opens.
+#[derive(Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum StartPage {
+    #[default]
+    Home,
+    Library,
+    /// The most recently watched episode, or the home page if there is none.
+    ContinueWatching,
+}
+
+/// Preferences edited from the settings page.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub theme: Theme,
+    pub font: Font,
+    pub player: PlayerPreferences,
+    pub feedback: FeedbackMode,
+    pub image_cache: ImageCacheSize,
+    pub start_page: StartPage,
+}
+
+impl AppSettings {
+    const KEY: &str = "nero.settings";
+
+    pub fn load() -> Self {
+        load(Self::KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        save(Self::KEY, self);
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use js_sys::Date;
+use serde::{Deserialize, Serialize};
+
+use crate::subtitles::SubtitleFormat;
+
+use super::{load, save_without_backup};
+
+/// A subtitle file loaded by the user.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SubtitleFile {
+    pub name: String,
+    pub format: SubtitleFormat,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredFile {
+    #[serde(flatten)]
+    file: SubtitleFile,
+    /// When the file was loaded, files saved before it was recorded are the
+    /// first ones evicted.
+    #[serde(default)]
+    saved_at: f64,
+}
+
+/// Subtitle files remembered for the episode they were loaded on.
+pub struct EpisodeSubtitles;
+
+impl EpisodeSubtitles {
+    const KEY: &str = "nero.subtitles";
+    /// Total length of the files kept, so they don't fill the storage quota.
+    /// The oldest ones are evicted past it.
+    const MAX_TOTAL_SIZE: usize = 1024 * 1024;
+    /// Largest file that can be remembered, files are refused past it.
+    pub const MAX_FILE_SIZE: usize = Self::MAX_TOTAL_SIZE;
+
+    fn files() -> HashMap<String, StoredFile> {
+        load(Self::KEY).unwrap_or_default()
+    }
+
+    pub fn get(episode_key: &str) -> Option<SubtitleFile> {
+        Self::files().remove(episode_key).map(|stored| stored.file)
+    }
+
+    /// Remembers `file` for the episode, unless it's larger than
+    /// [`Self::MAX_FILE_SIZE`]. Returns whether it was remembered.
+    pub fn set(episode_key: &str, file: SubtitleFile) -> bool {
+        let mut files = Self::files();
+        files.remove(episode_key);
+        let fits = file.content.len() <= Self::MAX_FILE_SIZE;
+        if fits {
+            files.insert(
+                episode_key.to_owned(),
+                StoredFile {
+                    file,
+                    saved_at: Date::now(),
+                },
+            );
+        }
+
+        let mut total = files
+            .values()
+            .map(|stored| stored.file.content.len())
+            .sum::<usize>();
+        while total > Self::MAX_TOTAL_SIZE {
+            let Some(oldest) = files
+                .iter()
+                .min_by(|(_, a), (_, b)| a.saved_at.total_cmp(&b.saved_at))
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            if let Some(stored) = files.remove(&oldest) {
+                total -= stored.file.content.len();
+            }
+        }
+
+        save_without_backup(Self::KEY, &files);
+        fits
+    }
+
+    pub fn remove(episode_key: &str) {
+        let mut files = Self::files();
+        if files.remove(episode_key).is_some() {
+            save_without_backup(Self::KEY, &files);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::cell::RefCell;
+
+use serde::Serialize;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use web_sys::{console, HtmlMediaElement, HtmlVideoElement};
+
+use crate::{
+    tauri::{self, invoke},
+    types::{SubtitleTrack, Video},
+};
+
+thread_local! {
+    /// Base URL of the streaming proxy, returned by nero-app once streams are
+    /// declared.
+    static PROXY_BASE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// Binding to the `Hls` class of hls.js, loaded from `index.html`.
+    pub type Hls;
+
+    #[wasm_bindgen(constructor)]
+    fn new() -> Hls;
+
+    #[wasm_bindgen(static_method_of = Hls, js_name = isSupported)]
+    fn is_supported() -> bool;
+
+    #[wasm_bindgen(method, js_name = loadSource)]
+    fn load_source(this: &Hls, url: &str);
+
+    #[wasm_bindgen(method, js_name = attachMedia)]
+    fn attach_media(this: &Hls, media: &HtmlMediaElement);
+
+    #[wasm_bindgen(method)]
+    fn destroy(this: &Hls);
+
+    /// Binding to the `MediaPlayer` of dash.js, loaded from `index.html`.
+    pub type DashPlayerFactory;
+
+    pub type DashPlayer;
+
+    #[wasm_bindgen(js_namespace = dashjs, js_name = MediaPlayer)]
+    fn dash_player_factory() -> DashPlayerFactory;
+
+    #[wasm_bindgen(method)]
+    fn create(this: &DashPlayerFactory) -> DashPlayer;
+
+    #[wasm_bindgen(method)]
+    fn initialize(this: &DashPlayer, media: &HtmlMediaElement, url: &str, auto_play: bool);
+
+    #[wasm_bindgen(method)]
+    fn reset(this: &DashPlayer);
+}
+
+#[derive(Serialize)]
+struct DeclaredStream<'a> {
+    url: &'a str,
+    headers: &'a [(String, String)],
+}
+
+#[derive(Serialize)]
+struct DeclaredStreams<'a> {
+    streams: Vec<DeclaredStream<'a>>,
+}
+
+/// Declares the hosts and headers of `videos` and their subtitle tracks to the
+/// streaming proxy, which refuses any other. Their [`source_url`]s and
+/// [`track_url`]s must only be loaded once it completes.
+pub async fn declare_streams(videos: &[Video]) {
+    if !tauri::is_available() {
+        return;
+    }
+
+    let streams = videos
+        .iter()
+        .filter(|video| !video.headers.is_empty())
+        .flat_map(|video| {
+            // Subtitles are fetched with the headers of their video
+            let tracks = video.tracks.iter().map(|track| &track.url);
+            std::iter::once(&video.url)
+                .chain(tracks)
+                .map(|url| DeclaredStream {
+                    url,
+                    headers: &video.headers,
+                })
+        })
+        .collect();
+    let args = serde_wasm_bindgen::to_value(&DeclaredStreams { streams }).unwrap_or_default();
+
+    match invoke("declare_streams", args).await {
+        Ok(base) => PROXY_BASE.set(base.as_string()),
+        Err(err) => console::warn_2(&"Couldn't declare the streams to the proxy".into(), &err),
+    }
+}
+
+/// Returns the URL the player should load for `video`.
+///
+/// Videos requiring headers are routed through the app's streaming proxy, since
+/// `<video>` sources can't carry custom headers. Outside of Tauri (e.g. `trunk serve`),
+/// or before the streams are declared, the URL is returned as is.
+pub fn source_url(video: &Video) -> String {
+    proxied_url(&video.url, &video.headers)
+}
+
+/// Returns the URL the subtitles of `track`, one of the tracks of `video`, should
+/// be fetched from, routed through the proxy like [`source_url`].
+pub fn track_url(video: &Video, track: &SubtitleTrack) -> String {
+    proxied_url(&track.url, &video.headers)
+}
+
+fn proxied_url(url: &str, headers: &[(String, String)]) -> String {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return url.to_owned();
+    };
+    let Some(base) = PROXY_BASE.with_borrow(Clone::clone) else {
+        return url.to_owned();
+    };
+    if headers.is_empty() {
+        return url.to_owned();
+    }
+
+    let headers = serde_json::to_string(headers).unwrap_or_default();
+    format!(
+        "{base}/{}/{scheme}/{rest}",
+        js_sys::encode_uri_component(&headers)
+    )
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum StreamType {
+    /// Any format the `<video>` element is expected to play on its own (e.g. mp4, webm).
+    Native,
+    /// HTTP Live Streaming playlist (`.m3u8`).
+    Hls,
+    /// MPEG-DASH manifest (`.mpd`).
+    Dash,
+}
+
+impl StreamType {
+    /// Guesses the stream type from the extension of the URL path.
+    pub fn detect(url: &str) -> Self {
+        let path = url
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if path.ends_with(".m3u8") {
+            StreamType::Hls
+        } else if path.ends_with(".mpd") {
+            StreamType::Dash
+        } else {
+            StreamType::Native
+        }
+    }
+}
+
+/// Playback of a stream attached to a `<video>` element.
+pub enum Playback {
+    Native,
+    Hls(Hls),
+    Dash(DashPlayer),
+}
+
+impl Playback {
+    /// Starts loading `url` into `video`, going through hls.js or dash.js when the
+    /// stream needs it, and falling back to native playback when they are unavailable.
+    pub fn attach(video: &HtmlVideoElement, url: &str) -> Self {
+        match StreamType::detect(url) {
+            // WebKit based webviews can play HLS natively
+            StreamType::Hls
+                if video
+                    .can_play_type("application/vnd.apple.mpegurl")
+                    .is_empty()
+                    && is_global_defined("Hls")
+                    && Hls::is_supported() =>
+            {
+                let hls = Hls::new();
+                hls.load_source(url);
+                hls.attach_media(video);
+                Playback::Hls(hls)
+            }
+            StreamType::Dash if is_global_defined("dashjs") => {
+                let player = dash_player_factory().create();
+                player.initialize(video, url, false);
+                Playback::Dash(player)
+            }
+            _ => {
+                video.set_src(url);
+                Playback::Native
+            }
+        }
+    }
+
+    /// Releases the resources held by the streaming library, if any.
+    pub fn detach(self) {
+        match self {
+            Playback::Native => {}
+            Playback::Hls(hls) => hls.destroy(),
+            Playback::Dash(player) => player.reset(),
+        }
+    }
+}
+
+fn is_global_defined(name: &str) -> bool {
+    js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str(name)).unwrap_or(false)
+}
@@ -0,0 +1,448 @@
+//! Advanced SubStation Alpha parsing, covering styles, positioning and basic
+//! karaoke. Other effects (e.g. transforms, clipping) are ignored, and drawings
+//! are skipped.
+
+use std::collections::HashMap;
+
+/// Resolution scripts are authored for when `PlayResX`/`PlayResY` are missing.
+const DEFAULT_PLAY_RES: (f64, f64) = (384.0, 288.0);
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct AssColor {
+    r: u8,
+    g: u8,
+    b: u8,
+    /// Opacity, where `255` is fully opaque (ASS stores transparency instead).
+    a: u8,
+}
+
+impl AssColor {
+    const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    /// Parses `&HAABBGGRR&` or, as in SSA scripts, a decimal `BBGGRR` value.
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().trim_end_matches('&');
+        let value = match value
+            .strip_prefix("&H")
+            .or_else(|| value.strip_prefix("&h"))
+        {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => value.parse::<i64>().ok()? as u32,
+        };
+
+        Some(Self {
+            r: value as u8,
+            g: (value >> 8) as u8,
+            b: (value >> 16) as u8,
+            a: 255 - (value >> 24) as u8,
+        })
+    }
+
+    pub fn css(&self) -> String {
+        format!(
+            "rgba({}, {}, {}, {:.3})",
+            self.r,
+            self.g,
+            self.b,
+            self.a as f64 / 255.0
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct AssStyle {
+    pub font_name: String,
+    pub font_size: f64,
+    pub primary: AssColor,
+    /// Color of karaoke syllables before they are sung.
+    pub secondary: AssColor,
+    pub outline: AssColor,
+    pub outline_width: f64,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strike_out: bool,
+    /// Numpad position of the text, e.g. `2` for bottom center.
+    pub alignment: u8,
+    pub margin_l: f64,
+    pub margin_r: f64,
+    pub margin_v: f64,
+}
+
+impl Default for AssStyle {
+    fn default() -> Self {
+        AssStyle {
+            font_name: "Arial".to_owned(),
+            font_size: 20.0,
+            primary: AssColor::rgb(255, 255, 255),
+            secondary: AssColor::rgb(255, 0, 0),
+            outline: AssColor::rgb(0, 0, 0),
+            outline_width: 2.0,
+            bold: false,
+            italic: false,
+            underline: false,
+            strike_out: false,
+            alignment: 2,
+            margin_l: 10.0,
+            margin_r: 10.0,
+            margin_v: 10.0,
+        }
+    }
+}
+
+impl AssStyle {
+    fn from_fields(fields: &Fields, legacy: bool) -> Self {
+        let default = AssStyle::default();
+        let number = |name: &str, default: f64| {
+            fields
+                .get(name)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(default)
+        };
+        let color = |name: &str, default: AssColor| {
+            fields
+                .get(name)
+                .and_then(AssColor::parse)
+                .unwrap_or(default)
+        };
+        // Booleans are stored as -1 (true) and 0 (false)
+        let flag = |name: &str| number(name, 0.0) != 0.0;
+
+        let alignment = number("alignment", 2.0) as u8;
+
+        AssStyle {
+            font_name: fields
+                .get("fontname")
+                .map(str::to_owned)
+                .unwrap_or(default.font_name),
+            font_size: number("fontsize", default.font_size),
+            primary: color("primarycolour", default.primary),
+            secondary: color("secondarycolour", default.secondary),
+            outline: color("outlinecolour", default.outline),
+            outline_width: number("outline", default.outline_width),
+            bold: flag("bold"),
+            italic: flag("italic"),
+            underline: flag("underline"),
+            strike_out: flag("strikeout"),
+            alignment: match legacy {
+                true => legacy_alignment(alignment),
+                false => alignment,
+            },
+            margin_l: number("marginl", default.margin_l),
+            margin_r: number("marginr", default.margin_r),
+            margin_v: number("marginv", default.margin_v),
+        }
+    }
+}
+
+/// Converts SSA alignments (1-3 bottom, 5-7 top, 9-11 middle) to numpad ones.
+fn legacy_alignment(alignment: u8) -> u8 {
+    match alignment {
+        5..=7 => alignment + 2,
+        9..=11 => alignment - 5,
+        _ => alignment,
+    }
+}
+
+pub struct AssEvent {
+    /// Start time in seconds.
+    pub start: f64,
+    /// End time in seconds.
+    pub end: f64,
+    pub style: String,
+    /// Text including override tags, e.g. `{\an8}Hello`.
+    pub text: String,
+}
+
+/// A piece of text sharing the same style.
+pub struct AssSegment {
+    pub text: String,
+    pub style: AssStyle,
+    /// Start and duration in seconds of the karaoke syllable, relative to the event start.
+    pub karaoke: Option<(f64, f64)>,
+}
+
+/// An event once its override tags are applied.
+pub struct AssLine {
+    pub alignment: u8,
+    /// Explicit position of the anchor point set with `\pos`, in script pixels.
+    pub position: Option<(f64, f64)>,
+    pub segments: Vec<AssSegment>,
+}
+
+impl AssEvent {
+    pub fn layout(&self, base: &AssStyle) -> AssLine {
+        let mut line = AssLine {
+            alignment: base.alignment,
+            position: None,
+            segments: Vec::new(),
+        };
+        let mut style = base.clone();
+        let mut karaoke = None;
+        let mut karaoke_end = 0.0;
+        // Text in drawing mode is made of vector commands, not dialogue
+        let mut drawing = false;
+
+        let mut rest = self.text.as_str();
+        while !rest.is_empty() {
+            if let Some(block) = rest.strip_prefix('{') {
+                let (tags, after) = block.split_once('}').unwrap_or((block, ""));
+                for tag in tags.split('\\').filter(|tag| !tag.is_empty()) {
+                    if let Some(duration) = karaoke_duration(tag) {
+                        karaoke = Some((karaoke_end, duration));
+                        karaoke_end += duration;
+                    } else if let Some(scale) = drawing_scale(tag) {
+                        drawing = scale != 0;
+                    } else {
+                        apply_tag(tag, &mut style, &mut line, base);
+                    }
+                }
+                rest = after;
+            } else {
+                let end = rest.find('{').unwrap_or(rest.len());
+                if drawing {
+                    karaoke = None;
+                    rest = &rest[end..];
+                    continue;
+                }
+                line.segments.push(AssSegment {
+                    text: rest[..end]
+                        .replace("\\N", "\n")
+                        .replace("\\n", " ")
+                        .replace("\\h", "\u{a0}"),
+                    style: style.clone(),
+                    karaoke: karaoke.take(),
+                });
+                rest = &rest[end..];
+            }
+        }
+
+        line
+    }
+
+    /// Text of the event without any styling.
+    pub fn plain_text(&self) -> String {
+        self.layout(&AssStyle::default())
+            .segments
+            .into_iter()
+            .map(|segment| segment.text)
+            .collect()
+    }
+}
+
+/// Parses `\k`, `\K`, `\kf` and `\ko` tags, whose duration is in centiseconds.
+fn karaoke_duration(tag: &str) -> Option<f64> {
+    let duration = tag
+        .strip_prefix("kf")
+        .or_else(|| tag.strip_prefix("ko"))
+        .or_else(|| tag.strip_prefix('k'))
+        .or_else(|| tag.strip_prefix('K'))?;
+    duration.parse::<f64>().ok().map(|cs| cs / 100.0)
+}
+
+/// Parses `\p` tags, where a non-zero scale starts drawing mode and `0` ends it.
+fn drawing_scale(tag: &str) -> Option<u32> {
+    tag.strip_prefix('p')?.trim().parse().ok()
+}
+
+fn apply_tag(tag: &str, style: &mut AssStyle, line: &mut AssLine, base: &AssStyle) {
+    let number = |value: &str| value.trim().parse::<f64>().ok();
+    let flag = |value: &str| number(value).map(|value| value != 0.0);
+    // An empty value resets the color to the one of the style
+    let color = |value: &str, current: AssColor, default: AssColor| match value {
+        "" => default,
+        value => AssColor::parse(value).unwrap_or(current),
+    };
+
+    // Longer tags sharing a prefix with supported ones (e.g. `\bord` and `\b`) come first
+    if let Some(value) = tag.strip_prefix("bord") {
+        style.outline_width = number(value).unwrap_or(base.outline_width);
+    } else if let Some(value) = tag.strip_prefix("pos(") {
+        let mut coordinates = value.trim_end_matches(')').split(',').filter_map(number);
+        if let (Some(x), Some(y)) = (coordinates.next(), coordinates.next()) {
+            line.position = Some((x, y));
+        }
+    } else if let Some(value) = tag.strip_prefix("an") {
+        line.alignment = number(value).map_or(line.alignment, |alignment| alignment as u8);
+    } else if let Some(value) = tag.strip_prefix("fn") {
+        style.font_name = value.to_owned();
+    } else if let Some(value) = tag.strip_prefix("fs") {
+        // Skips `\fscx`, `\fscy` and `\fsp`, which have no numeric value right after `fs`
+        if let Some(size) = number(value) {
+            style.font_size = size;
+        }
+    } else if let Some(value) = tag.strip_prefix("1c").or_else(|| tag.strip_prefix('c')) {
+        style.primary = color(value, style.primary, base.primary);
+    } else if let Some(value) = tag.strip_prefix("2c") {
+        style.secondary = color(value, style.secondary, base.secondary);
+    } else if let Some(value) = tag.strip_prefix("3c") {
+        style.outline = color(value, style.outline, base.outline);
+    } else if let Some(value) = tag.strip_prefix('b') {
+        // Weights such as `\b700` are treated as bold
+        style.bold = flag(value).unwrap_or(style.bold);
+    } else if let Some(value) = tag.strip_prefix('i') {
+        style.italic = flag(value).unwrap_or(style.italic);
+    } else if let Some(value) = tag.strip_prefix('u') {
+        style.underline = flag(value).unwrap_or(style.underline);
+    } else if let Some(value) = tag.strip_prefix('s') {
+        style.strike_out = flag(value).unwrap_or(style.strike_out);
+    } else if let Some(value) = tag.strip_prefix('a') {
+        if let Some(alignment) = number(value) {
+            line.alignment = legacy_alignment(alignment as u8);
+        }
+    } else if tag.starts_with('r') {
+        *style = base.clone();
+    }
+}
+
+/// Splits a `Style:` or `Dialogue:` line according to the `Format:` of its section.
+struct Fields<'a> {
+    format: &'a [String],
+    values: Vec<&'a str>,
+}
+
+impl<'a> Fields<'a> {
+    fn new(format: &'a [String], line: &'a str) -> Self {
+        // The last field (e.g. the dialogue text) may contain commas itself
+        let values = line
+            .splitn(format.len().max(1), ',')
+            .map(str::trim)
+            .collect();
+        Self { format, values }
+    }
+
+    fn get(&self, name: &str) -> Option<&'a str> {
+        let index = self.format.iter().position(|field| field == name)?;
+        self.values.get(index).copied()
+    }
+}
+
+pub struct AssScript {
+    /// Resolution positions, margins and font sizes are expressed in.
+    pub play_res: (f64, f64),
+    styles: HashMap<String, AssStyle>,
+    pub events: Vec<AssEvent>,
+}
+
+impl AssScript {
+    pub fn parse(content: &str) -> Self {
+        enum Section {
+            ScriptInfo,
+            Styles { legacy: bool },
+            Events,
+            Other,
+        }
+
+        let mut section = Section::Other;
+        let mut format = Vec::<String>::new();
+        let (mut res_x, mut res_y) = (None::<f64>, None::<f64>);
+        let mut styles = HashMap::new();
+        let mut events = Vec::new();
+
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') {
+                section = match line.to_lowercase().as_str() {
+                    "[script info]" => Section::ScriptInfo,
+                    "[v4+ styles]" => Section::Styles { legacy: false },
+                    "[v4 styles]" => Section::Styles { legacy: true },
+                    "[events]" => Section::Events,
+                    _ => Section::Other,
+                };
+                format.clear();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match (&section, key.trim()) {
+                (Section::ScriptInfo, "PlayResX") => res_x = value.parse().ok(),
+                (Section::ScriptInfo, "PlayResY") => res_y = value.parse().ok(),
+                (Section::Styles { .. } | Section::Events, "Format") => {
+                    format = value
+                        .split(',')
+                        .map(|field| field.trim().to_lowercase())
+                        .collect();
+                }
+                (Section::Styles { legacy }, "Style") => {
+                    let fields = Fields::new(&format, value);
+                    if let Some(name) = fields.get("name") {
+                        let name = name.trim_start_matches('*').to_owned();
+                        styles.insert(name, AssStyle::from_fields(&fields, *legacy));
+                    }
+                }
+                (Section::Events, "Dialogue") => {
+                    let fields = Fields::new(&format, value);
+                    let (Some(start), Some(end), Some(text)) = (
+                        fields.get("start").and_then(parse_time),
+                        fields.get("end").and_then(parse_time),
+                        fields.get("text"),
+                    ) else {
+                        continue;
+                    };
+
+                    events.push(AssEvent {
+                        start,
+                        end,
+                        style: fields
+                            .get("style")
+                            .unwrap_or_default()
+                            .trim_start_matches('*')
+                            .to_owned(),
+                        text: text.to_owned(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        events.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        // When only one dimension is given, the other one is derived assuming 4:3
+        let play_res = match (res_x, res_y) {
+            (Some(x), Some(y)) => (x, y),
+            (Some(x), None) => (x, x * 3.0 / 4.0),
+            (None, Some(y)) => (y * 4.0 / 3.0, y),
+            (None, None) => DEFAULT_PLAY_RES,
+        };
+
+        Self {
+            play_res,
+            styles,
+            events,
+        }
+    }
+
+    /// Returns the style named `name`, falling back to the `Default` one.
+    pub fn style(&self, name: &str) -> AssStyle {
+        self.styles
+            .get(name)
+            .or_else(|| self.styles.get("Default"))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the events shown at `time`, in seconds, with their index.
+    pub fn active_events(&self, time: f64) -> impl Iterator<Item = (usize, &AssEvent)> {
+        self.events
+            .iter()
+            .enumerate()
+            .filter(move |(_, event)| event.start <= time && time < event.end)
+    }
+}
+
+/// Parses `H:MM:SS.cc` into seconds.
+fn parse_time(time: &str) -> Option<f64> {
+    let mut parts = time.trim().split(':');
+    let (hours, minutes, seconds) = (parts.next()?, parts.next()?, parts.next()?);
+
+    Some(
+        hours.parse::<f64>().ok()? * 3600.0
+            + minutes.parse::<f64>().ok()? * 60.0
+            + seconds.parse::<f64>().ok()?,
+    )
+}
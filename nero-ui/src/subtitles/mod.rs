@@ -0,0 +1,113 @@
+//! Subtitle file support. Files are converted to WebVTT, the only format
+//! `<track>` elements support, except ASS scripts which are rendered by
+//! `SubtitleOverlay` to keep their styling.
+
+mod ass;
+
+pub use ass::*;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, Response, Url};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SubtitleFormat {
+    Vtt,
+    Srt,
+    /// Advanced SubStation Alpha, also covering the older SSA format.
+    Ass,
+}
+
+impl SubtitleFormat {
+    /// File types accepted by the subtitle file picker.
+    pub const ACCEPT: &str = ".vtt,.srt,.ass,.ssa";
+
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let (_, extension) = name.rsplit_once('.')?;
+        match extension.to_lowercase().as_str() {
+            "vtt" => Some(Self::Vtt),
+            "srt" => Some(Self::Srt),
+            "ass" | "ssa" => Some(Self::Ass),
+            _ => None,
+        }
+    }
+}
+
+/// Converts the content of a subtitle file to WebVTT.
+pub fn to_vtt(content: &str, format: SubtitleFormat) -> String {
+    let content = content.trim_start_matches('\u{feff}');
+
+    match format {
+        SubtitleFormat::Vtt => content.to_owned(),
+        SubtitleFormat::Srt => srt_to_vtt(content),
+        SubtitleFormat::Ass => ass_to_vtt(content),
+    }
+}
+
+/// Downloads the subtitle file at `url`, returning its content.
+pub async fn fetch(url: &str) -> Option<String> {
+    let window = web_sys::window()?;
+    let response: Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .ok()?
+        .unchecked_into();
+    if !response.ok() {
+        return None;
+    }
+
+    let content = JsFuture::from(response.text().ok()?)
+        .await
+        .ok()?
+        .as_string()?;
+    Some(content.trim_start_matches('\u{feff}').to_owned())
+}
+
+/// Creates a URL `<track>` elements can load the WebVTT content from.
+/// It must be released with `Url::revoke_object_url` once unused.
+pub fn object_url(vtt: &str) -> Option<String> {
+    let options = BlobPropertyBag::new();
+    options.set_type("text/vtt");
+
+    let parts = js_sys::Array::of1(&vtt.into());
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options).ok()?;
+    Url::create_object_url_with_blob(&blob).ok()
+}
+
+/// SRT only differs from WebVTT by its header and the decimal separator of timestamps.
+fn srt_to_vtt(content: &str) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+    for line in content.lines() {
+        match line.contains("-->") {
+            true => vtt.push_str(&line.replace(',', ".")),
+            false => vtt.push_str(line),
+        }
+        vtt.push('\n');
+    }
+    vtt
+}
+
+/// Keeps the timing and text of each event, dropping the styling WebVTT can't represent.
+fn ass_to_vtt(content: &str) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+    for event in AssScript::parse(content).events {
+        vtt.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            vtt_timestamp(event.start),
+            vtt_timestamp(event.end),
+            event.plain_text()
+        ));
+    }
+    vtt
+}
+
+fn vtt_timestamp(seconds: f64) -> String {
+    let millis = (seconds * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
@@ -0,0 +1,27 @@
+//! Bindings to the APIs nero-app provides to the webview, missing when the UI runs
+//! on its own (e.g. `trunk serve`).
+
+use serde::Serialize;
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = ["__TAURI__", "core"])]
+    pub async fn invoke(command: &str, args: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Serialize)]
+struct OpenArgs<'a> {
+    path: &'a str,
+}
+
+/// Whether the UI runs inside nero-app.
+pub fn is_available() -> bool {
+    js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("__TAURI__")).unwrap_or(false)
+}
+
+/// Opens `url` with the default browser of the system, through the shell plugin.
+pub async fn open(url: &str) -> Result<(), JsValue> {
+    let args = serde_wasm_bindgen::to_value(&OpenArgs { path: url })?;
+    invoke("plugin:shell|open", args).await.map(|_| ())
+}
@@ -1,11 +1,19 @@
 #![allow(dead_code)]
 
+use crate::subtitles::SubtitleFormat;
+
+#[derive(Clone)]
 pub struct Series {
     pub id: String,
     pub title: String,
+    /// Other titles of the series, e.g. romanized or translated.
+    pub alternative_titles: Vec<String>,
+    pub genres: Vec<String>,
     pub poster_url: Option<String>,
     pub synopsis: Option<String>,
     pub r#type: Option<String>,
+    /// YouTube or direct video URL of the trailer.
+    pub trailer_url: Option<String>,
 }
 
 impl Default for Series {
@@ -13,6 +21,8 @@ impl Default for Series {
         Series {
             id: "spy-x-family".to_owned(),
             title: "SPY x FAMILY".to_owned(),
+            alternative_titles: vec!["Supai Famirī".to_owned()],
+            genres: vec!["Action".to_owned(), "Comedy".to_owned()],
             poster_url: Some("https://m.media-amazon.com/images/M/MV5BZjNjN2UyYTYtMjY2Zi00ZWFlLWFmMDItZTNkMzQ3MDc1Yjg5XkEyXkFqcGc@._V1_.jpg".to_owned()),
             synopsis: Some(r#"
                 World peace is at stake and secret agent Twilight must undergo his most difficult mission yet—
@@ -22,22 +32,140 @@ impl Default for Series {
                 telepath!
             "#.to_owned()),
             r#type: Some("Series".to_owned()),
+            trailer_url: Some(
+                "http://commondatastorage.googleapis.com/gtv-videos-bucket/sample/ForBiggerFun.mp4"
+                    .to_owned(),
+            ),
+        }
+    }
+}
+
+pub struct SeriesPage {
+    pub series: Vec<Series>,
+    pub has_next_page: bool,
+}
+
+impl Default for SeriesPage {
+    fn default() -> Self {
+        SeriesPage {
+            series: (0..12).map(|_| Series::default()).collect(),
+            has_next_page: true,
         }
     }
 }
 
+/// How the value of a `FilterCategory` is selected.
+#[derive(Clone, Copy)]
+pub enum FilterKind {
+    MultiSelect,
+    SingleSelect,
+    Toggle,
+    /// Inclusive bounds of the allowed range.
+    Range(i32, i32),
+    Text,
+}
+
+/// A group of filters that can be applied to a search, e.g. "Genre".
+pub struct FilterCategory {
+    pub id: String,
+    pub display_name: String,
+    pub kind: FilterKind,
+    /// Pairs of displayable name and filter value, only used by select kinds.
+    pub filters: Vec<(String, String)>,
+}
+
+impl FilterCategory {
+    fn new(id: &str, display_name: &str, kind: FilterKind, filters: &[(&str, &str)]) -> Self {
+        FilterCategory {
+            id: id.to_owned(),
+            display_name: display_name.to_owned(),
+            kind,
+            filters: filters
+                .iter()
+                .map(|&(name, value)| (name.to_owned(), value.to_owned()))
+                .collect(),
+        }
+    }
+
+    pub fn samples() -> Vec<Self> {
+        vec![
+            FilterCategory::new(
+                "genre",
+                "Genre",
+                FilterKind::MultiSelect,
+                &[
+                    ("Action", "action"),
+                    ("Adventure", "adventure"),
+                    ("Comedy", "comedy"),
+                    ("Drama", "drama"),
+                    ("Fantasy", "fantasy"),
+                    ("Horror", "horror"),
+                    ("Isekai", "isekai"),
+                    ("Mecha", "mecha"),
+                    ("Music", "music"),
+                    ("Mystery", "mystery"),
+                    ("Psychological", "psychological"),
+                    ("Romance", "romance"),
+                    ("Sci-fi", "sci_fi"),
+                    ("Slice of life", "slice_of_life"),
+                    ("Sports", "sports"),
+                    ("Supernatural", "supernatural"),
+                    ("Thriller", "thriller"),
+                ],
+            ),
+            FilterCategory::new(
+                "status",
+                "Status",
+                FilterKind::SingleSelect,
+                &[("Airing", "airing"), ("Finished", "finished")],
+            ),
+            FilterCategory::new("dubbed", "Dubbed only", FilterKind::Toggle, &[]),
+            FilterCategory::new("year", "Year", FilterKind::Range(1960, 2025), &[]),
+            FilterCategory::new("studio", "Studio", FilterKind::Text, &[]),
+        ]
+    }
+}
+
+/// Value chosen for a `FilterCategory`, matching its `FilterKind`.
+#[derive(Clone, PartialEq)]
+pub enum FilterValue {
+    Selected(Vec<String>),
+    Toggle(bool),
+    Range(i32, i32),
+    Text(String),
+}
+
+/// Filter identifier paired with its chosen value, as sent to a search.
+pub type SearchFilter = (String, FilterValue);
+
+#[derive(Clone)]
 pub struct Episode {
     pub id: String,
+    /// Id of the series the episode belongs to, since episode ids are only unique within it.
+    pub series_id: String,
     pub number: u16,
     pub title: Option<String>,
     pub thumbnail_url: Option<String>,
     pub description: Option<String>,
+    /// Watched fraction between `0.0` and `1.0`, if the episode was started.
+    pub progress: Option<f64>,
+}
+
+impl Episode {
+    pub fn sample(number: u16) -> Self {
+        Episode {
+            id: number.to_string(),
+            number,
+            ..Episode::default()
+        }
+    }
 }
 
 impl Default for Episode {
     fn default() -> Self {
         Episode {
             id: "1".to_owned(),
+            series_id: String::new(),
             number: 1,
             title: Some("OPERATION STRIX".to_owned()),
             thumbnail_url: Some("https://m.media-amazon.com/images/M/MV5BZDM0ZmU3MDAtZThmNy00MmY1LTliNjQtM2M5MWU3MGJiOGU5XkEyXkFqcGc@._V1_.jpg".to_owned()),
@@ -49,18 +177,43 @@ impl Default for Episode {
                 who can read people's minds. One day, members of a mafia group that is after Twilight kidnaps Anya. 
                 Loid realizes that he needs to reconsider his priorities and...
             "#.to_owned()),
+            progress: None,
         }
     }
 }
 
+/// Subtitles provided by the source along with a video.
+#[derive(Clone)]
+pub struct SubtitleTrack {
+    pub url: String,
+    /// Language tag of the subtitles, e.g. `en`.
+    pub language: String,
+    pub format: SubtitleFormat,
+}
+
+#[derive(Clone)]
 pub struct Video {
     pub url: String,
-    // TODO: headers,
+    /// HTTP headers required to access the video (e.g. Referer).
+    pub headers: Vec<(String, String)>,
     pub server: String,
     pub resolution: (u16, u16),
+    /// Subtitles provided by the source, fetched with the video's headers.
+    pub tracks: Vec<SubtitleTrack>,
 }
 
 impl Video {
+    /// Returns the sample video served from a few servers and resolutions.
+    pub fn samples() -> Vec<Self> {
+        [("google", 1080), ("google", 720), ("mirror", 480)]
+            .map(|(server, height)| Video {
+                server: server.to_owned(),
+                resolution: (height, height * 16 / 9),
+                ..Video::default()
+            })
+            .to_vec()
+    }
+
     pub const VIDEO_TITLE: &str = "Big Buck Bunny";
     pub const VIDEO_SYNOPSIS: Option<&str> = Some(
         r#"
@@ -76,8 +229,10 @@ impl Default for Video {
             url:
                 "http://commondatastorage.googleapis.com/gtv-videos-bucket/sample/BigBuckBunny.mp4"
                     .to_owned(),
+            headers: Vec::new(),
             server: "google".to_owned(),
             resolution: (0, 0),
+            tracks: Vec::new(),
         }
     }
 }
@@ -1,8 +1,15 @@
 /** @type {import('tailwindcss').Config} */
 module.exports = {
   content: ["../target/classes.txt"],
+  // Not listed in `classes.txt` since rustwind has no variant for custom families
+  safelist: ["font-dyslexic"],
   theme: {
-    extend: {},
+    extend: {
+      fontFamily: {
+        // Bundled OpenDyslexic, see `styles.css`
+        dyslexic: ["OpenDyslexic", "ui-sans-serif", "sans-serif"],
+      },
+    },
   },
   plugins: [],
 }